@@ -23,13 +23,91 @@ SOFTWARE.
 */
 
 use delaunator::{triangulate, Point as DelaunatorPoint};
-use geo::{Point as GeoPoint, Coord};
-use rand::Rng;
+use geo::{Point as GeoPoint, Area, BooleanOps, Contains, ConvexHull, Coord, Intersects, LineString, MultiPoint, MultiPolygon, Polygon};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::distributions::Distribution;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
-use std::cmp::{min, max};
-use std::collections::{HashMap, HashSet};
+use rstar::{primitives::{GeomWithData, Rectangle}, RTree, AABB};
+use statrs::distribution::Poisson;
+use std::cmp::{min, max, Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::ops::Sub;
+use std::sync::OnceLock;
+#[cfg(feature = "parallel")]
 use std::sync::{Arc, Mutex};
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum XenoError {
+    AreasNotComputed,
+    InvalidMeshLength(usize),
+    VertexOutOfRange { vertex: usize, points: usize },
+    Io(String),
+    Parse { line: usize, content: String },
+    ScalarsMismatch { scalars: usize, points: usize },
+    SamplingExhausted { generated: u32, requested: u32 },
+    UnknownSnapshot(usize),
+    SelfIntersectingHull,
+    NoPoints,
+}
+
+impl fmt::Display for XenoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XenoError::AreasNotComputed => write!(f, "triangle areas were not computed, preprocess with types 0 or 2"),
+            XenoError::InvalidMeshLength(len) => write!(f, "triangle index buffer length {} is not divisible by 3", len),
+            XenoError::VertexOutOfRange { vertex, points } => write!(f, "vertex index {} out of range for {} points", vertex, points),
+            XenoError::Io(message) => write!(f, "i/o error: {}", message),
+            XenoError::Parse { line, content } => write!(f, "malformed coordinates on line {}: {:?}", line, content),
+            XenoError::ScalarsMismatch { scalars, points } => write!(f, "{} scalars for {} points", scalars, points),
+            XenoError::SamplingExhausted { generated, requested } => write!(f, "only {} of {} points found outside the holes", generated, requested),
+            XenoError::UnknownSnapshot(t) => write!(f, "no snapshot stored for timestep {}", t),
+            XenoError::SelfIntersectingHull => write!(f, "concave hull boundary is not a simple polygon"),
+            XenoError::NoPoints => write!(f, "no points to triangulate"),
+        }
+    }
+}
+
+impl std::error::Error for XenoError {}
+
+impl From<std::io::Error> for XenoError {
+    fn from(error: std::io::Error) -> Self {
+        XenoError::Io(error.to_string())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    #[default]
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+    GreatCircle, // Points are (lon, lat) in degrees, lengths are central angles in degrees
+}
+
+// What preprocess computes, the named form of its `types` argument
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PreprocessMode {
+    #[default]
+    Full,
+    Attractors,
+    Voids,
+}
+
+impl PreprocessMode {
+    fn types(self) -> usize {
+        match self {
+            PreprocessMode::Full => 0,
+            PreprocessMode::Attractors => 1,
+            PreprocessMode::Voids => 2,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Point {
     pub x: f32,
@@ -39,7 +117,7 @@ pub struct Point {
 impl Point {
 
     pub fn new(x: f32, y: f32) -> Self {
-        Point{ x: x, y: y }
+        Point{ x, y }
     }
 
     pub fn from_geo32(point: GeoPoint<f32>) -> Self {
@@ -51,7 +129,49 @@ impl Point {
     }
 
     pub fn distance(&self,point: Point) -> f32 {
-        ( (point.x - &self.x).powi(2) + (point.y - &self.y).powi(2) ).sqrt()
+        ( (point.x - self.x).powi(2) + (point.y - self.y).powi(2) ).sqrt()
+    }
+
+    pub fn distance_l1(&self, point: Point) -> f32 {
+        (point.x - self.x).abs() + (point.y - self.y).abs()
+    }
+
+    pub fn distance_linf(&self, point: Point) -> f32 {
+        (point.x - self.x).abs().max((point.y - self.y).abs())
+    }
+
+    pub fn distance_metric(&self, point: Point, metric: Metric) -> f32 {
+        match metric {
+            Metric::Euclidean => self.distance(point),
+            Metric::Manhattan => self.distance_l1(point),
+            Metric::Chebyshev => self.distance_linf(point),
+            Metric::GreatCircle => self.distance_great_circle(point),
+        }
+    }
+
+    // Haversine central angle in degrees between two (lon, lat) points
+    pub fn distance_great_circle(&self, point: Point) -> f32 {
+        let (lat1, lat2) = ((self.y as f64).to_radians(), (point.y as f64).to_radians());
+        let delta_lat = lat2 - lat1;
+        let delta_lon = ((point.x - self.x) as f64).to_radians();
+        let h = (delta_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+        (2.0 * h.sqrt().min(1.0).asin()).to_degrees() as f32
+    }
+
+    // Area of the spherical triangle between three (lon, lat) points in square
+    // degrees, from the spherical excess (Van Oosterom and Strackee)
+    pub fn spherical_area(a: Point, b: Point, c: Point) -> f32 {
+        let (a, b, c) = (unit_vector(a), unit_vector(b), unit_vector(c));
+        let dot = |u: [f64; 3], v: [f64; 3]| u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+        let triple = a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0]) + a[2] * (b[0] * c[1] - b[1] * c[0]);
+        let excess = 2.0 * triple.abs().atan2(1.0 + dot(a, b) + dot(b, c) + dot(c, a));
+        (excess * (180.0 / std::f64::consts::PI).powi(2)) as f32
+    }
+
+    // Half the cross product (b - a) x (c - a): positive when a, b, c turn
+    // counter-clockwise, negative when clockwise, zero when collinear
+    pub fn signed_area(a: Point, b: Point, c: Point) -> f32 {
+        ((b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)) / 2.0
     }
 
     pub fn bearing(&self, point: Point) -> f32 {
@@ -60,8 +180,7 @@ impl Point {
         let angle = delta_y.atan2(delta_x).to_degrees();
     
         // Convert Cartesian degree to compass bearing
-        let bearing = (angle + 360.0) % 360.0;
-        bearing
+        (angle + 360.0) % 360.0
     }
 
     pub fn bearing_rad(&self, point: Point) -> f32 {
@@ -72,13 +191,52 @@ impl Point {
 
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct Point3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32
+}
+
+impl Point3 {
+
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Point3{ x, y, z }
+    }
+
+    pub fn distance(&self, point: Point3) -> f32 {
+        ( (point.x - self.x).powi(2) + (point.y - self.y).powi(2) + (point.z - self.z).powi(2) ).sqrt()
+    }
+
+    pub fn cross(&self, point: Point3) -> Point3 {
+        Point3 {
+            x: self.y * point.z - self.z * point.y,
+            y: self.z * point.x - self.x * point.z,
+            z: self.x * point.y - self.y * point.x,
+        }
+    }
+
+    pub fn dot(&self, point: Point3) -> f32 {
+        self.x * point.x + self.y * point.y + self.z * point.z
+    }
+
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, point: Point3) -> Point3 {
+        Point3 { x: self.x - point.x, y: self.y - point.y, z: self.z - point.z }
+    }
+}
+
 impl From<Point> for Coord<f32> {
     fn from(point: Point) -> Self {
         Coord { x: point.x, y: point.y }
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Edge(pub usize, pub usize);
 
 #[derive(Debug, Default, Clone)]
@@ -86,33 +244,78 @@ pub struct TriangleData {
     pub index: usize,
     pub area: Option<f32>,
     pub terminal_edge: Option<Edge>,
-    pub vertices: Vec<usize>
+    pub vertices: Vec<usize>,
+    pub edges: [Edge; 3]
 }
 
 impl TriangleData {
-    pub fn get_edges(&self) -> Vec<Edge> {
-        let mut edges = Vec::new();
+    // Edges are cached by add_triangle, empty slots have none
+    pub fn get_edges(&self) -> &[Edge] {
         if self.vertices.len() >= 3 {
-            for i in 0..self.vertices.len() {
-                let v1 = self.vertices[i];
-                let v2 = if i + 1 < self.vertices.len() {
-                    self.vertices[i + 1]
-                } else {
-                    self.vertices[0]
-                };
-                edges.push(if v1 < v2 { Edge(v1, v2) } else { Edge(v2, v1) });
-            }
+            &self.edges
+        } else {
+            &[]
         }
-        edges
     }
 }
 
+// Shape descriptors from the covariance of member coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeMetrics {
+    pub elongation: f32, // major / minor eigenvalue ratio, 1 for isotropic
+    pub compactness: f32, // minor / major axis ratio in [0, 1]
+    pub principal_axis_bearing: f32, // major axis orientation in degrees, 0-180
+}
+
+// Largest void area statistics over random trials
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoidStats {
+    pub mean_max_area: f32,
+    pub std_max_area: f32,
+    pub n_trials: usize,
+}
+
+// A void followed across increasing min_distance thresholds
+#[derive(Debug, Clone, Copy)]
+pub struct VoidTrack {
+    pub birth: f32, // first threshold the void is found at
+    pub death: Option<f32>, // first threshold it is gone at, None when it lasts to the end
+    pub centroid: Point, // at the last threshold it was found at
+    pub area: f32, // at the last threshold it was found at
+}
+
+// Triangles with a smaller interior angle (degrees) count as slivers
+pub const SLIVER_ANGLE: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshReport {
+    pub n_points: usize,
+    pub n_triangles: usize,
+    pub n_edges: usize,
+    pub min_angle: f32, // degrees
+    pub max_angle: f32, // degrees
+    pub min_area: f32,
+    pub max_area: f32,
+    pub n_boundary_edges: usize,
+    pub n_sliver_triangles: usize,
+}
+
+// Per-triangle label of a combined void / cluster segmentation, ids index the inputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriangleClass {
+    Void(usize),
+    Cluster(usize),
+    Background,
+}
+
 #[derive(Debug)]
 pub struct GeometryData {
     pub triangles: Vec<TriangleData>,
     pub edge_to_triangles: HashMap<Edge, Vec<usize>>, // Maps an edge to triangle indices
     pub edge_lengths: HashMap<Edge, f32>, // Edge lengths
     pub vertex_connections: HashMap<usize, HashSet<usize>>, // Direct connections between vertices, for DTSCAN
+    pub vertex_edges: HashMap<usize, Vec<Edge>>, // Edges incident to each vertex, built on demand
+    centroid_index: OnceLock<RTree<GeomWithData<[f32; 2], usize>>>, // Triangle centroids, built on the first triangles_near
 }
 
 impl GeometryData {
@@ -122,9 +325,42 @@ impl GeometryData {
             edge_to_triangles: HashMap::new(),
             edge_lengths: HashMap::new(),
             vertex_connections: HashMap::new(), // Adjusted for DTSCAN
+            vertex_edges: HashMap::new(),
+            centroid_index: OnceLock::new(),
+        }
+    }
+
+    // A planar triangulation of n points has about 2n triangles and 3n edges
+    fn with_capacity(n_points: usize) -> Self {
+        GeometryData {
+            triangles: Vec::with_capacity(2 * n_points),
+            edge_to_triangles: HashMap::with_capacity(3 * n_points),
+            edge_lengths: HashMap::with_capacity(3 * n_points),
+            vertex_connections: HashMap::with_capacity(n_points),
+            vertex_edges: HashMap::new(),
+            centroid_index: OnceLock::new(),
         }
     }
-    fn add_triangle(&mut self, index: usize, points: &[Point], tri_idx: &[usize], types: usize) {
+
+    // Empties all tables while keeping their allocations
+    fn clear(&mut self) {
+        self.triangles.clear();
+        self.edge_to_triangles.clear();
+        self.edge_lengths.clear();
+        self.vertex_connections.clear();
+        self.vertex_edges.clear();
+        self.centroid_index = OnceLock::new();
+    }
+
+    // Grows the tables to hold a triangulation of n points in total
+    fn reserve(&mut self, n_points: usize) {
+        self.triangles.reserve((2 * n_points).saturating_sub(self.triangles.len()));
+        self.edge_to_triangles.reserve((3 * n_points).saturating_sub(self.edge_to_triangles.len()));
+        self.edge_lengths.reserve((3 * n_points).saturating_sub(self.edge_lengths.len()));
+        self.vertex_connections.reserve(n_points.saturating_sub(self.vertex_connections.len()));
+    }
+
+    fn add_triangle(&mut self, index: usize, points: &[Point], tri_idx: &[usize], types: usize, metric: Metric) {
 
         let point_a: Point = points[tri_idx[0]];
         let point_b: Point = points[tri_idx[1]];
@@ -132,38 +368,43 @@ impl GeometryData {
 
         let mut vertices = vec![tri_idx[0], tri_idx[1], tri_idx[2]];
         vertices.sort_unstable();
+        let edges = [
+            Edge(vertices[0], vertices[1]),
+            Edge(vertices[1], vertices[2]),
+            Edge(vertices[0], vertices[2]),
+        ];
+
+        // Reuse the length of edges already seen from the neighboring triangle
+        let edge_length = |edge: Edge, p: Point, q: Point| {
+            self.edge_lengths.get(&edge).copied().unwrap_or_else(|| p.distance_metric(q, metric))
+        };
+        let edge_ab = Edge(min(tri_idx[0], tri_idx[1]), max(tri_idx[0], tri_idx[1]));
+        let edge_bc = Edge(min(tri_idx[1], tri_idx[2]), max(tri_idx[1], tri_idx[2]));
+        let edge_ca = Edge(min(tri_idx[2], tri_idx[0]), max(tri_idx[2], tri_idx[0]));
 
         // Temporarily store edges_with_lengths for sorting and determining the terminal_edge.
         let mut edges_with_lengths_temp = [
-            (Edge(min(tri_idx[0], tri_idx[1]), max(tri_idx[0], tri_idx[1])), point_a.distance(point_b)),
-            (Edge(min(tri_idx[1], tri_idx[2]), max(tri_idx[1], tri_idx[2])), point_b.distance(point_c)),
-            (Edge(min(tri_idx[2], tri_idx[0]), max(tri_idx[2], tri_idx[0])), point_c.distance(point_a)),
+            (edge_ab, edge_length(edge_ab, point_a, point_b)),
+            (edge_bc, edge_length(edge_bc, point_b, point_c)),
+            (edge_ca, edge_length(edge_ca, point_c, point_a)),
         ].to_vec();
         
-        // Sort edges by length to ensure the longest edge is identified.
-        edges_with_lengths_temp.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        // Sort edges by length to ensure the longest edge is identified,
+        // equal lengths fall back to edge order so the choice is deterministic.
+        edges_with_lengths_temp.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then_with(|| a.0.cmp(&b.0)));
         
         let terminal_edge: Option<Edge> = edges_with_lengths_temp.first().map(|(edge, _)| *edge);
         
         let area: Option<f32> = if types == 0 || types == 2 {
-            let x1 = point_a.x;
-            let y1 = point_a.y;
-            let x2 = point_b.x;
-            let y2 = point_b.y;
-            let x3 = point_c.x;
-            let y3 = point_c.y;
-        
-            // Calculate the area using the shoelace formula
-            let calculated_area = (x1*(y2-y3) + x2*(y3-y1) + x3*(y1-y2)).abs() / 2.0;
-            Some(calculated_area)
+            Some(triangle_area(point_a, point_b, point_c, metric))
         } else {
             None
         };        
     
         if types == 0 || types == 1 {
             for &(edge, length) in &edges_with_lengths_temp {
-                self.vertex_connections.entry(edge.0).or_insert_with(HashSet::new).insert(edge.1);
-                self.vertex_connections.entry(edge.1).or_insert_with(HashSet::new).insert(edge.0);
+                self.vertex_connections.entry(edge.0).or_default().insert(edge.1);
+                self.vertex_connections.entry(edge.1).or_default().insert(edge.0);
                 self.edge_lengths.insert(edge, length);
                 self.edge_to_triangles.entry(edge).or_default().push(index);
             }
@@ -175,27 +416,138 @@ impl GeometryData {
             }
         }
 
-        // Dynamically resize the struct in memory to accomodate the index
-        if index >= self.triangles.len() {
-            self.triangles.resize(index + 1, TriangleData::default());
-        }
-    
+        // Only store triangle data when areas are requested, so connectivity-only
+        // runs leave no placeholder slots. Every index is added exactly once, so
+        // slots opened out of order by the parallel preprocess are always filled.
         if types == 0 || types == 2 {
+            if index >= self.triangles.len() {
+                self.triangles.resize(index + 1, TriangleData::default());
+            }
             self.triangles[index] = TriangleData {
                 index,
                 area,
                 terminal_edge,
-                vertices
+                vertices,
+                edges
             };
         }
     } 
           
 }
 
+// Reusable dtscan state for sweeping max_closeness on fixed geometry. A dtscan
+// cluster is the component, over edges no longer than max_closeness, of a core
+// vertex, so queries only union a prefix of the length-sorted edges. Raising
+// the threshold between queries continues from the previous unions.
+pub struct DtscanSession {
+    vertices: Vec<usize>,
+    degree: Vec<usize>,
+    max_incident: Vec<f32>,
+    edges: Vec<(f32, usize, usize)>,
+    parent: Vec<usize>,
+    merged: usize,
+}
+
+impl DtscanSession {
+    fn new(geometry_data: &GeometryData) -> Self {
+        let mut vertices: Vec<usize> = geometry_data.vertex_connections.keys().copied().collect();
+        vertices.sort_unstable();
+        let slot: HashMap<usize, usize> = vertices.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+
+        let mut degree = vec![0; vertices.len()];
+        let mut max_incident = vec![0.0f32; vertices.len()];
+        for (i, &v) in vertices.iter().enumerate() {
+            let neighbors = &geometry_data.vertex_connections[&v];
+            degree[i] = neighbors.len();
+            for &n in neighbors {
+                let length = geometry_data.edge_lengths.get(&Edge(min(v, n), max(v, n))).copied().unwrap_or(f32::INFINITY);
+                max_incident[i] = max_incident[i].max(length);
+            }
+        }
+
+        let mut edges: Vec<(f32, usize, usize)> = geometry_data.edge_lengths.iter()
+            .filter_map(|(edge, &length)| Some((length, *slot.get(&edge.0)?, *slot.get(&edge.1)?)))
+            .collect();
+        edges.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let parent = (0..vertices.len()).collect();
+        DtscanSession { vertices, degree, max_incident, edges, parent, merged: 0 }
+    }
+
+    fn find(&mut self, v: usize) -> usize {
+        let mut root = v;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = v;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    // Same clusters as Xenobalanus::dtscan, clusters ordered by their smallest vertex
+    pub fn query(&mut self, min_pts: usize, max_closeness: f32) -> Vec<Vec<usize>> {
+        let limit = self.edges.partition_point(|e| e.0 <= max_closeness);
+        if limit < self.merged {
+            self.parent = (0..self.vertices.len()).collect();
+            self.merged = 0;
+        }
+        for k in self.merged..limit {
+            let (_, a, b) = self.edges[k];
+            let (root_a, root_b) = (self.find(a), self.find(b));
+            if root_a != root_b {
+                self.parent[root_a] = root_b;
+            }
+        }
+        self.merged = limit;
+
+        let mut has_core: HashSet<usize> = HashSet::new();
+        for i in 0..self.vertices.len() {
+            if self.degree[i] >= min_pts && self.max_incident[i] <= max_closeness {
+                let root = self.find(i);
+                has_core.insert(root);
+            }
+        }
+
+        let mut clusters: Vec<Vec<usize>> = Vec::new();
+        let mut cluster_of: HashMap<usize, usize> = HashMap::new();
+        for i in 0..self.vertices.len() {
+            let root = self.find(i);
+            if has_core.contains(&root) {
+                let id = *cluster_of.entry(root).or_insert_with(|| {
+                    clusters.push(Vec::new());
+                    clusters.len() - 1
+                });
+                clusters[id].push(self.vertices[i]);
+            }
+        }
+        clusters
+    }
+}
+
 pub struct Xenobalanus {
     geometry_data: GeometryData,
     points: Vec<Point>,
+    points_3d: Vec<Point3>,
+    tetrahedra: Vec<usize>,
     triangulation: Vec<usize>,
+    halfedges: Vec<usize>,
+    metric: Metric,
+    scalars: Vec<f32>,
+    min_triangles: usize,
+    degenerate: bool,
+    snapshots: HashMap<usize, Vec<Point>>,
+    void_mask: HashSet<usize>,
+    barriers: HashSet<Edge>,
+}
+
+impl Default for Xenobalanus {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Xenobalanus {
@@ -203,8 +555,61 @@ impl Xenobalanus {
         Xenobalanus {
             geometry_data: GeometryData::new(),
             points: Vec::new(),
+            points_3d: Vec::new(),
+            tetrahedra: Vec::new(),
             triangulation: Vec::new(),
+            halfedges: Vec::new(),
+            metric: Metric::default(),
+            scalars: Vec::new(),
+            min_triangles: 3,
+            degenerate: false,
+            snapshots: HashMap::new(),
+            void_mask: HashSet::new(),
+            barriers: HashSet::new(),
+        }
+    }
+
+    pub fn builder() -> XenobalanusBuilder {
+        XenobalanusBuilder::default()
+    }
+
+    // Points generated or appended (random_points, lattice_points, add_points) fill
+    // the preallocated buffer, set_points replaces it. The lookup tables keep their
+    // size across sequential preprocess runs.
+    pub fn with_capacity(n_points: usize) -> Self {
+        Xenobalanus {
+            geometry_data: GeometryData::with_capacity(n_points),
+            points: Vec::with_capacity(n_points),
+            ..Self::new()
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.points.reserve(additional);
+        self.geometry_data.reserve(self.points.len() + additional);
+    }
+
+    // Reads `x y` or `x,y` pairs line by line, blank lines and # comments are skipped
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, XenoError> {
+        let mut xeno = Xenobalanus::new();
+        for (number, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let values: Vec<f32> = trimmed
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|field| !field.is_empty())
+                .map(|field| field.parse::<f32>())
+                .collect::<Result<_, _>>()
+                .map_err(|_| XenoError::Parse { line: number + 1, content: line.clone() })?;
+            match values[..] {
+                [x, y] => xeno.points.push(Point::new(x, y)),
+                _ => return Err(XenoError::Parse { line: number + 1, content: line }),
+            }
         }
+        Ok(xeno)
     }
 
     pub fn point(&self, index: usize) -> Point {
@@ -223,10 +628,159 @@ impl Xenobalanus {
             .collect()
     }
 
+    // Lower-left and upper-right corners of the point set
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        let first = *self.points.first()?;
+        Some(self.points.iter().fold((first, first), |(lo, hi), p| {
+            (Point::new(lo.x.min(p.x), lo.y.min(p.y)), Point::new(hi.x.max(p.x), hi.y.max(p.y)))
+        }))
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    // Edge lengths are measured under this metric on the next preprocess
+    pub fn set_metric(&mut self, metric: Metric) {
+        self.metric = metric
+    }
+
+    pub fn min_triangles(&self) -> usize {
+        self.min_triangles
+    }
+
+    // Smallest number of triangles a delfin void may consist of, 3 by default
+    pub fn set_min_triangles(&mut self, min_triangles: usize) {
+        self.min_triangles = min_triangles
+    }
+
+    // Triangles delfin never seeds from or grows into, e.g. known survey artifacts.
+    // Indices refer to the current triangulation, the mask is cleared when it is rebuilt.
+    pub fn set_void_mask(&mut self, excluded: HashSet<usize>) {
+        self.void_mask = excluded
+    }
+
+    // Triangulation edges delfin never grows a void across, e.g. walls in a floorplan.
+    // Cleared when points are added or removed, e.g. by add_points or subsample.
+    pub fn set_barriers(&mut self, edges: Vec<Edge>) {
+        self.barriers = edges.into_iter()
+            .map(|Edge(a, b)| Edge(min(a, b), max(a, b)))
+            .collect()
+    }
+
     pub fn set_points(&mut self, points: Vec<Point>) {
         self.points = points
     }
 
+    // Appends points, the triangulation has to be rebuilt afterwards
+    pub fn add_points(&mut self, points: Vec<Point>) {
+        self.points.extend(points);
+        self.invalidate();
+    }
+
+    pub fn points_3d(&self) -> Vec<(f32, f32, f32)> {
+        self.points_3d.iter()
+            .map(|point| (point.x, point.y, point.z))
+            .collect()
+    }
+
+    // Replaces the 3D points, tetrahedra set on the old points are dropped
+    pub fn set_points_3d(&mut self, points: Vec<Point3>) {
+        self.points_3d = points;
+        self.tetrahedra.clear();
+    }
+
+    // Alpha shape of a points_3d subset: the subset is tetrahedralized and the
+    // boundary faces of the tetrahedra whose circumsphere radius is below 1/alpha
+    // are returned, wound counter-clockwise when seen from outside. alpha <= 0
+    // keeps the convex hull.
+    pub fn concave_hull_3d(&self, vertices: Vec<usize>, alpha: f32) -> Vec<[usize; 3]> {
+        let mut vertices: Vec<usize> = vertices.into_iter().filter(|&v| v < self.points_3d.len()).collect();
+        vertices.sort_unstable();
+        vertices.dedup();
+        let points: Vec<Point3> = vertices.iter().map(|&v| self.points_3d[v]).collect();
+        let max_radius = if alpha > 0.0 { 1.0 / alpha as f64 } else { f64::INFINITY };
+        let tetrahedra: Vec<[usize; 4]> = tetrahedralize(&points).into_iter()
+            .filter(|t| {
+                circumsphere_f64(points[t[0]], points[t[1]], points[t[2]], points[t[3]])
+                    .is_some_and(|(_, r2)| r2.sqrt() < max_radius)
+            })
+            .collect();
+        unshared_faces(&tetrahedra).into_iter()
+            .map(|(face, owner)| outward_face(&points, face, &tetrahedra[owner]).map(|i| vertices[i]))
+            .collect()
+    }
+
+    // Delaunay tetrahedralization of points_3d into the set_tetrahedra buffer, the 3D
+    // counterpart of delaunay(). Coplanar input gives no tetrahedra.
+    pub fn delaunay_3d(&mut self) {
+        self.tetrahedra = tetrahedralize(&self.points_3d).into_iter().flatten().collect();
+    }
+
+    // Flat index buffer of four points_3d indices per tetrahedron, as produced by
+    // delaunay_3d or an external 3D Delaunay library
+    pub fn set_tetrahedra(&mut self, vertices: Vec<usize>) {
+        self.tetrahedra = vertices
+    }
+
+    // Volume from the scalar triple product of the edges leaving v1
+    pub fn tetrahedron_volume(&self, v1: usize, v2: usize, v3: usize, v4: usize) -> f32 {
+        let a = self.points_3d[v1];
+        let (u, v, w) = (self.points_3d[v2] - a, self.points_3d[v3] - a, self.points_3d[v4] - a);
+        u.dot(v.cross(w)).abs() / 6.0
+    }
+
+    // Tetrahedra with a face no other tetrahedron shares. Tetrahedra referring to a
+    // vertex outside points_3d (the infinite vertex of some libraries) or repeating
+    // a vertex are skipped.
+    pub fn boundary_tetrahedra(&self) -> Vec<usize> {
+        let mut tetrahedra: Vec<usize> = self.boundary_face_owners(|_| true).into_iter()
+            .map(|(_, owner)| owner)
+            .collect();
+        tetrahedra.sort_unstable();
+        tetrahedra.dedup();
+        tetrahedra
+    }
+
+    // Unshared faces, wound counter-clockwise when seen from outside
+    pub fn boundary_faces(&self) -> Vec<[usize; 3]> {
+        self.boundary_face_owners(|_| true).into_iter()
+            .map(|(face, owner)| outward_face(&self.points_3d, face, &self.tetrahedra[owner * 4..owner * 4 + 4]))
+            .collect()
+    }
+
+    // Sorted boundary faces of the set_tetrahedra tetrahedra accepted by `include`,
+    // each with the tetrahedron it belongs to
+    fn boundary_face_owners(&self, include: impl Fn(usize) -> bool) -> Vec<([usize; 3], usize)> {
+        let (owners, tetrahedra): (Vec<usize>, Vec<[usize; 4]>) = self.tetrahedra.chunks_exact(4)
+            .enumerate()
+            .filter(|&(index, t)| t.iter().all(|&v| v < self.points_3d.len()) && include(index))
+            .map(|(index, t)| (index, [t[0], t[1], t[2], t[3]]))
+            .unzip();
+        unshared_faces(&tetrahedra).into_iter()
+            .map(|(face, owner)| (face, owners[owner]))
+            .collect()
+    }
+
+    // Summed volume of the void's tetrahedra, indices into the set_tetrahedra buffer.
+    // Tetrahedra referring to a vertex outside points_3d add nothing.
+    pub fn void_volume_3d(&self, void: &HashSet<usize>) -> f32 {
+        void.iter()
+            .filter_map(|&i| self.tetrahedra.get(i * 4..i * 4 + 4))
+            .filter(|t| t.iter().all(|&v| v < self.points_3d.len()))
+            .map(|t| self.tetrahedron_volume(t[0], t[1], t[2], t[3]) as f64)
+            .sum::<f64>() as f32
+    }
+
+    pub fn scalars(&self) -> &[f32] {
+        &self.scalars
+    }
+
+    // One scalar field value per point
+    pub fn set_scalars(&mut self, scalars: Vec<f32>) {
+        self.scalars = scalars
+    }
+
     pub fn triangle(&self, index: usize) -> TriangleData {
         self.geometry_data.triangles[index].clone()
     }
@@ -235,13 +789,20 @@ impl Xenobalanus {
         &self.geometry_data.triangles
     }
 
+    // Only triangles that carry data, empty placeholder slots are skipped
+    pub fn iter_triangles(&self) -> impl Iterator<Item = (usize, &TriangleData)> {
+        self.geometry_data.triangles.iter()
+            .enumerate()
+            .filter(|(_, t)| t.vertices.len() == 3)
+    }
+
     pub fn triangles_flat(&self) -> Vec<usize> {
         self.triangulation.clone()
     }
 
     pub fn triangle_vertices(&self) -> Vec<Vec<usize>> {
         self.triangulation.chunks(3).map(|chunk| {
-            chunk.iter().map(|&index| index).collect()
+            chunk.to_vec()
         }).collect()
     }
 
@@ -255,7 +816,56 @@ impl Xenobalanus {
     }
 
     pub fn set_triangles(&mut self, vertices: Vec<usize>) {
-        self.triangulation = vertices
+        self.halfedges.clear();
+        self.triangulation = vertices;
+        self.degenerate = self.triangulation.is_empty();
+        self.void_mask.clear();
+    }
+
+    pub fn random_points_sphere(&mut self, center: (f32, f32, f32), radius: f32, num_points: u32, seed: u64) {
+        // Uniform on the sphere: z uniform in [-1, 1] and azimuth uniform, which
+        // avoids the clustering at the poles that sampling both angles causes
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..num_points {
+            let z: f32 = rng.gen_range(-1.0..=1.0);
+            let phi: f32 = rng.gen_range(0.0..std::f32::consts::TAU);
+            let r = (1.0 - z * z).max(0.0).sqrt();
+            self.points_3d.push(Point3 {
+                x: center.0 + radius * r * phi.cos(),
+                y: center.1 + radius * r * phi.sin(),
+                z: center.2 + radius * z,
+            });
+        }
+    }
+
+    pub fn set_snapshot(&mut self, t: usize, points: Vec<Point>) {
+        self.snapshots.insert(t, points);
+    }
+
+    // Loads snapshot t as the current point set, triangulates it and runs delfin
+    pub fn analyze_snapshot(&mut self, t: usize, min_area: f32, min_distance: f32) -> Result<Vec<HashSet<usize>>, XenoError> {
+        let points = self.snapshots.get(&t).ok_or(XenoError::UnknownSnapshot(t))?.clone();
+        self.points = points;
+        self.scalars.clear();
+        self.invalidate();
+        self.delaunay();
+        self.preprocess(0, false);
+        self.delfin(min_area, min_distance)
+    }
+
+    // Import an existing mesh, preprocess can be called directly afterwards
+    pub fn set_mesh(&mut self, points: Vec<Point>, triangles: Vec<usize>) -> Result<(), XenoError> {
+        if !triangles.len().is_multiple_of(3) {
+            return Err(XenoError::InvalidMeshLength(triangles.len()));
+        }
+        if let Some(&vertex) = triangles.iter().find(|&&v| v >= points.len()) {
+            return Err(XenoError::VertexOutOfRange { vertex, points: points.len() });
+        }
+        self.points = points;
+        self.scalars.clear();
+        self.invalidate();
+        self.set_triangles(triangles);
+        Ok(())
     }
 
     // Additional methods moved into GeometryProcessor, operating on self.geometry_data
@@ -273,79 +883,457 @@ impl Xenobalanus {
         }
     }
 
-    pub fn edge_lengths(&self) -> &HashMap<Edge, f32> {
-        &self.geometry_data.edge_lengths
-    }
+    pub fn subsample(&mut self, target: usize, seed: u64) -> Vec<usize> {
+        let n = self.points.len();
+        let (lo, hi) = match self.bounding_box() {
+            Some(bounds) if target < n => bounds,
+            _ => return (0..n).collect(),
+        };
 
-    pub fn delaunay(&mut self) {
-        // Convert geo::Point to delaunator::Point for triangulation
-        let delaunator_points: Vec<DelaunatorPoint> = self.points.iter()
-        .map(|point: &Point| DelaunatorPoint { x: point.x as f64, y: point.y as f64 })
-        .collect();
+        // Stratify on a grid with roughly one kept point per cell
+        let cells = (target as f32).sqrt().ceil().max(1.0) as usize;
+        let width = (hi.x - lo.x).max(f32::EPSILON);
+        let height = (hi.y - lo.y).max(f32::EPSILON);
+        let mut grid: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (index, point) in self.points.iter().enumerate() {
+            let cx = (((point.x - lo.x) / width) * cells as f32).min(cells as f32 - 1.0) as usize;
+            let cy = (((point.y - lo.y) / height) * cells as f32).min(cells as f32 - 1.0) as usize;
+            grid.entry((cx, cy)).or_default().push(index);
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let ratio = target as f64 / n as f64;
+        let mut cell_keys: Vec<(usize, usize)> = grid.keys().copied().collect();
+        cell_keys.sort_unstable();
+
+        let mut kept: HashSet<usize> = HashSet::new();
+        for key in cell_keys {
+            let members = grid.get_mut(&key).unwrap();
+            members.shuffle(&mut rng);
+            // Stochastic rounding keeps the expected total at the target
+            let keep = (members.len() as f64 * ratio + rng.gen::<f64>()).floor() as usize;
+            kept.extend(members.iter().take(keep));
+        }
+
+        // Keep the extreme points so the bounding box is preserved
+        for axis in [|p: &Point| p.x, |p: &Point| p.y] {
+            let by_axis = |a: &(usize, &Point), b: &(usize, &Point)| axis(a.1).partial_cmp(&axis(b.1)).unwrap();
+            if let Some((index, _)) = self.points.iter().enumerate().min_by(by_axis) {
+                kept.insert(index);
+            }
+            if let Some((index, _)) = self.points.iter().enumerate().max_by(by_axis) {
+                kept.insert(index);
+            }
+        }
 
-    // Perform Delaunay triangulation
-    let result: delaunator::Triangulation = triangulate(&delaunator_points);
-    self.triangulation = result.triangles
+        let mut kept: Vec<usize> = kept.into_iter().collect();
+        kept.sort_unstable();
+        self.points = kept.iter().map(|&i| self.points[i]).collect();
+        self.remap_scalars(&kept, n);
+        self.invalidate();
+        kept
     }
 
-    pub fn preprocess(&mut self, types: usize, parallel: bool) {
-        if parallel {
+    // Run before delaunay() to drop sentinel values and outliers
+    pub fn clip_to_bounds(&mut self, min: (f32, f32), max: (f32, f32)) -> usize {
+        let before = self.points.len();
+        let kept: Vec<usize> = (0..before)
+            .filter(|&i| {
+                let p = self.points[i];
+                p.x >= min.0 && p.x <= max.0 && p.y >= min.1 && p.y <= max.1
+            })
+            .collect();
+        let removed = before - kept.len();
+        if removed > 0 {
+            self.points = kept.iter().map(|&i| self.points[i]).collect();
+            self.remap_scalars(&kept, before);
+            self.invalidate();
+        }
+        removed
+    }
 
-            let geometry_data = Arc::new(Mutex::new(GeometryData::new()));
-        
-            self.triangulation.par_chunks(3).enumerate().for_each(|(index, tri_idx)| {
-                let gd = geometry_data.clone(); // Clone Arc for use in each thread, not the data itself
-        
-                // Perform locked update
-                let mut gd_lock = gd.lock().unwrap();
-                gd_lock.add_triangle(index, &self.points, tri_idx, types);
-            });
-        
-            self.geometry_data = Arc::try_unwrap(geometry_data).unwrap().into_inner().unwrap();
+    // Appends other's points after ours, so its point i becomes point
+    // len + i, and likewise for its points_3d and the tetrahedra on them.
+    // Both triangulations are dropped: call delaunay() and preprocess()
+    // again, cross-region edges only exist after that.
+    pub fn merge(&mut self, other: Xenobalanus) {
+        let scalars_aligned = self.scalars.len() == self.points.len()
+            && other.scalars.len() == other.points.len();
+        if scalars_aligned {
+            self.scalars.extend(other.scalars);
         } else {
+            self.scalars.clear();
+        }
+        self.points.extend(other.points);
+        // Vertices past other's points_3d stay past the merged ones
+        let offset = self.points_3d.len();
+        self.tetrahedra.extend(other.tetrahedra.iter().map(|&v| v.saturating_add(offset)));
+        self.points_3d.extend(other.points_3d);
+        self.invalidate();
+    }
 
-            self.triangulation.chunks(3).enumerate().for_each(|(index, tri_idx)| {
-                self.geometry_data.add_triangle(index, &self.points, tri_idx, types);
-            });
-
+    // Keeps the scalars of the surviving points, given the old indices in their new
+    // order and the old point count. Scalars that were out of step are dropped.
+    fn remap_scalars(&mut self, kept: &[usize], before: usize) {
+        if self.scalars.len() == before {
+            self.scalars = kept.iter().map(|&i| self.scalars[i]).collect();
+        } else {
+            self.scalars.clear();
         }
     }
 
-    pub fn delfin(
-        &self,
-        min_area: f32,
-        min_distance: f32,
-    ) -> Vec<HashSet<usize>> {
-        let mut void_polygons: Vec<HashSet<usize>> = Vec::new();
-        let mut processed_triangles: HashSet<usize> = HashSet::new();
-    
-        // Create a sorted list of triangles by their terminal edge length that meet the minimum distance criteria.
-        let mut triangles_sorted: Vec<(usize, f32)> = self.geometry_data.triangles.iter()
-            .filter_map(|t| t.terminal_edge.and_then(|e| self.geometry_data.edge_lengths.get(&e).map(|&l| (t.index, l))))
-            .filter(|&(_, length)| length >= min_distance)
-            .collect();
-    
-        // Sort by longest edge first
-        triangles_sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-    
-        // Iterate through triangles starting from the one with the longest terminal edge
-        for (triangle_index, _) in triangles_sorted {
+    // Drop the triangulation and lookup tables after the point set changes. Scalars
+    // no longer matching the points one to one are dropped as well.
+    fn invalidate(&mut self) {
+        if self.scalars.len() != self.points.len() {
+            self.scalars.clear();
+        }
+        self.triangulation.clear();
+        self.halfedges.clear();
+        self.degenerate = false;
+        self.void_mask.clear();
+        self.barriers.clear();
+        self.geometry_data.clear();
+    }
 
-            // Skip if already processed
-            if processed_triangles.contains(&triangle_index) {
+    // Rejection-samples the square, skipping points inside any (center, radius) hole.
+    // Gives up after 1000 draws per requested point, which only happens when the
+    // holes cover (nearly) all of the square; the points found so far are kept.
+    pub fn random_points_with_holes(
+        &mut self,
+        center: (f32, f32),
+        side_length: f32,
+        num_points: u32,
+        holes: &[(Point, f32)],
+        seed: u64,
+    ) -> Result<(), XenoError> {
+        let min_x = center.0 - side_length / 2.0;
+        let min_y = center.1 - side_length / 2.0;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut generated = 0;
+        let max_attempts = num_points as u64 * 1000;
+        for _ in 0..max_attempts {
+            if generated == num_points {
+                break;
+            }
+            let point = Point {
+                x: min_x + rng.gen_range(0.0..=1.0) as f32 * side_length,
+                y: min_y + rng.gen_range(0.0..=1.0) as f32 * side_length,
+            };
+            if holes.iter().any(|&(hole, radius)| point.distance(hole) < radius) {
                 continue;
             }
-            
-            let mut edges_to_expand: HashSet<Edge> = HashSet::new();
-            let mut current_set: HashSet<usize> = HashSet::new();
-            
-            // Seed the initial set and edges to expand
-            current_set.insert(triangle_index);
-            processed_triangles.insert(triangle_index);
+            self.points.push(point);
+            generated += 1;
+        }
+        if generated < num_points {
+            return Err(XenoError::SamplingExhausted { generated, requested: num_points });
+        }
+        Ok(())
+    }
+
+    // Row i holds the distances from point a[i] to every point in b
+    pub fn distance_matrix(&self, a: &[usize], b: &[usize]) -> Vec<Vec<f32>> {
+        #[cfg(feature = "parallel")]
+        let rows = a.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let rows = a.iter();
+
+        rows
+            .map(|&i| {
+                let point = self.points[i];
+                b.iter().map(|&j| point.distance(self.points[j])).collect()
+            })
+            .collect()
+    }
+
+    pub fn edge_lengths(&self) -> &HashMap<Edge, f32> {
+        &self.geometry_data.edge_lengths
+    }
+
+    pub fn edge_to_triangles(&self) -> &HashMap<Edge, Vec<usize>> {
+        &self.geometry_data.edge_to_triangles
+    }
+
+    pub fn vertex_connections(&self) -> &HashMap<usize, HashSet<usize>> {
+        &self.geometry_data.vertex_connections
+    }
+
+    pub fn triangulated_area(&self) -> Result<f32, XenoError> {
+        if self.geometry_data.triangles.is_empty() {
+            return Err(XenoError::AreasNotComputed);
+        }
+
+        // For a Delaunay triangulation this equals the area of the convex hull
+        self.geometry_data.triangles.iter()
+            .map(|t| t.area.ok_or(XenoError::AreasNotComputed))
+            .sum()
+    }
+
+    pub fn build_vertex_edge_index(&mut self) {
+        let mut vertex_edges: HashMap<usize, Vec<Edge>> = HashMap::new();
+        for &edge in self.geometry_data.edge_lengths.keys() {
+            vertex_edges.entry(edge.0).or_default().push(edge);
+            vertex_edges.entry(edge.1).or_default().push(edge);
+        }
+        for edges in vertex_edges.values_mut() {
+            edges.sort_unstable_by_key(|e| (e.0, e.1));
+        }
+        self.geometry_data.vertex_edges = vertex_edges;
+    }
+
+    pub fn vertex_edges(&self, vertex: usize) -> &[Edge] {
+        self.geometry_data.vertex_edges.get(&vertex).map_or(&[], |edges| edges.as_slice())
+    }
+
+    pub fn delaunay(&mut self) {
+        self.triangulate_prefix(self.points.len())
+    }
+
+    // Triangulates only the first n points, e.g. to animate the build-up. A larger n
+    // re-triangulates the longer prefix from scratch, delaunator has no insertion.
+    pub fn triangulate_prefix(&mut self, n: usize) {
+        // Convert geo::Point to delaunator::Point for triangulation
+        let delaunator_points: Vec<DelaunatorPoint> = self.points[..n.min(self.points.len())].iter()
+            .map(|point: &Point| DelaunatorPoint { x: point.x as f64, y: point.y as f64 })
+            .collect();
+
+        // Perform Delaunay triangulation
+        let result: delaunator::Triangulation = triangulate(&delaunator_points);
+        self.degenerate = result.triangles.is_empty();
+        self.triangulation = result.triangles;
+        self.halfedges = result.halfedges;
+        self.void_mask.clear();
+    }
+
+    // Opposite half-edge of each triangulation corner as delaunator reports it,
+    // delaunator::EMPTY on the hull. Only kept for triangulations from delaunay()
+    // and triangulate_prefix().
+    pub fn halfedges(&self) -> &[usize] {
+        &self.halfedges
+    }
+
+    // True when the last triangulation produced no triangles, i.e. fewer
+    // than three distinct points or all of them collinear
+    pub fn is_degenerate(&self) -> bool {
+        self.degenerate
+    }
+
+    // Tiles are triangulated in parallel. Tile triangles whose circumcircle stays
+    // inside their tile are globally Delaunay, the remaining seam region is
+    // re-triangulated from the seam vertices and filtered by the empty-circle test.
+    pub fn delaunay_chunked(&mut self, grid: (usize, usize)) {
+        let (lo, hi) = match self.bounding_box() {
+            Some(bounds) => bounds,
+            None => {
+                self.triangulation.clear();
+                self.degenerate = true;
+                return;
+            }
+        };
+        let (cols, rows) = (grid.0.max(1), grid.1.max(1));
+        let tile_w = (hi.x - lo.x) as f64 / cols as f64;
+        let tile_h = (hi.y - lo.y) as f64 / rows as f64;
+        let cell = |value: f64, size: f64, count: usize| {
+            if size > 0.0 { ((value / size) as usize).min(count - 1) } else { 0 }
+        };
+
+        let mut tiles: Vec<Vec<usize>> = vec![Vec::new(); cols * rows];
+        for (index, p) in self.points.iter().enumerate() {
+            let cx = cell((p.x - lo.x) as f64, tile_w, cols);
+            let cy = cell((p.y - lo.y) as f64, tile_h, rows);
+            tiles[cy * cols + cx].push(index);
+        }
+
+        let points = &self.points;
+        #[cfg(feature = "parallel")]
+        let tile_iter = tiles.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let tile_iter = tiles.iter();
+
+        let tile_results: Vec<(Vec<usize>, Vec<usize>)> = tile_iter.enumerate().map(|(tile, members)| {
+            let x0 = lo.x as f64 + (tile % cols) as f64 * tile_w;
+            let y0 = lo.y as f64 + (tile / cols) as f64 * tile_h;
+            let (x1, y1) = (x0 + tile_w, y0 + tile_h);
+
+            let local: Vec<DelaunatorPoint> = members.iter()
+                .map(|&i| DelaunatorPoint { x: points[i].x as f64, y: points[i].y as f64 })
+                .collect();
+            let result = triangulate(&local);
+
+            // Hull vertices border other tiles and always take part in the seam
+            let mut seam: Vec<usize> = result.hull.iter().map(|&i| members[i]).collect();
+            let mut safe: Vec<usize> = Vec::new();
+            for t in result.triangles.chunks(3) {
+                let tri = [members[t[0]], members[t[1]], members[t[2]]];
+                let contained = circumcircle_f64(points[tri[0]], points[tri[1]], points[tri[2]])
+                    .map(|(cx, cy, r2)| {
+                        let r = r2.sqrt();
+                        cx - r > x0 && cx + r < x1 && cy - r > y0 && cy + r < y1
+                    })
+                    .unwrap_or(false);
+                if contained {
+                    safe.extend_from_slice(&tri);
+                } else {
+                    seam.extend_from_slice(&tri);
+                }
+            }
+            (safe, seam)
+        }).collect();
+
+        let mut triangulation: Vec<usize> = Vec::new();
+        let mut is_seam = vec![false; self.points.len()];
+        for (safe, seam) in &tile_results {
+            triangulation.extend_from_slice(safe);
+            for &v in seam {
+                is_seam[v] = true;
+            }
+        }
+
+        // Safe triangles made only of seam vertices can reappear in the seam triangulation
+        let mut known: HashSet<[usize; 3]> = HashSet::new();
+        for t in triangulation.chunks(3) {
+            if t.iter().all(|&v| is_seam[v]) {
+                let mut key = [t[0], t[1], t[2]];
+                key.sort_unstable();
+                known.insert(key);
+            }
+        }
+
+        // Bucket the interior (non-seam) points for the empty-circle test
+        let interior: Vec<usize> = (0..self.points.len()).filter(|&i| !is_seam[i]).collect();
+        let spacing = (((hi.x - lo.x) as f64 * (hi.y - lo.y) as f64) / self.points.len() as f64).sqrt().max(f64::EPSILON) * 2.0;
+        let grid_cols = (((hi.x - lo.x) as f64 / spacing) as usize + 1).min(4096);
+        let grid_rows = (((hi.y - lo.y) as f64 / spacing) as usize + 1).min(4096);
+        let cell_w = (hi.x - lo.x) as f64 / grid_cols as f64;
+        let cell_h = (hi.y - lo.y) as f64 / grid_rows as f64;
+        let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); grid_cols * grid_rows];
+        for &i in &interior {
+            let cx = cell((self.points[i].x - lo.x) as f64, cell_w, grid_cols);
+            let cy = cell((self.points[i].y - lo.y) as f64, cell_h, grid_rows);
+            buckets[cy * grid_cols + cx].push(i);
+        }
+
+        let seam_points: Vec<usize> = (0..self.points.len()).filter(|&i| is_seam[i]).collect();
+        let local: Vec<DelaunatorPoint> = seam_points.iter()
+            .map(|&i| DelaunatorPoint { x: points[i].x as f64, y: points[i].y as f64 })
+            .collect();
+        let seam_result = triangulate(&local);
+
+        let keep_seam_triangle = |t: &[usize]| -> Vec<usize> {
+            let tri = [seam_points[t[0]], seam_points[t[1]], seam_points[t[2]]];
+            let mut key = tri;
+            key.sort_unstable();
+            if known.contains(&key) {
+                return Vec::new();
+            }
+            let (cx, cy, r2) = match circumcircle_f64(points[tri[0]], points[tri[1]], points[tri[2]]) {
+                Some(circle) => circle,
+                None => return Vec::new(),
+            };
+            let r = r2.sqrt();
+            let gx0 = cell((cx - r - lo.x as f64).max(0.0), cell_w, grid_cols);
+            let gx1 = cell((cx + r - lo.x as f64).max(0.0), cell_w, grid_cols);
+            let gy0 = cell((cy - r - lo.y as f64).max(0.0), cell_h, grid_rows);
+            let gy1 = cell((cy + r - lo.y as f64).max(0.0), cell_h, grid_rows);
+            let tolerance = r2 * 1e-9;
+            for gy in gy0..=gy1 {
+                for gx in gx0..=gx1 {
+                    for &i in &buckets[gy * grid_cols + gx] {
+                        let dx = points[i].x as f64 - cx;
+                        let dy = points[i].y as f64 - cy;
+                        if dx * dx + dy * dy < r2 - tolerance {
+                            return Vec::new();
+                        }
+                    }
+                }
+            }
+            tri.to_vec()
+        };
+
+        #[cfg(feature = "parallel")]
+        let seam_triangles: Vec<usize> = seam_result.triangles.par_chunks(3).flat_map_iter(keep_seam_triangle).collect();
+        #[cfg(not(feature = "parallel"))]
+        let seam_triangles: Vec<usize> = seam_result.triangles.chunks(3).flat_map(keep_seam_triangle).collect();
+
+        triangulation.extend(seam_triangles);
+        self.degenerate = triangulation.is_empty();
+        self.halfedges.clear();
+        self.triangulation = triangulation;
+        self.void_mask.clear();
+    }
+
+    // `parallel` has no effect when the crate is built without the `parallel` feature
+    pub fn preprocess(&mut self, types: usize, parallel: bool) {
+        #[cfg(feature = "parallel")]
+        if parallel {
+
+            let geometry_data = Arc::new(Mutex::new(GeometryData::with_capacity(self.points.len())));
+        
+            self.triangulation.par_chunks(3).enumerate().for_each(|(index, tri_idx)| {
+                let gd = geometry_data.clone(); // Clone Arc for use in each thread, not the data itself
+        
+                // Perform locked update
+                let mut gd_lock = gd.lock().unwrap();
+                gd_lock.add_triangle(index, &self.points, tri_idx, types, self.metric);
+            });
+        
+            self.geometry_data = Arc::try_unwrap(geometry_data).unwrap().into_inner().unwrap();
+            return;
+        }
+        #[cfg(not(feature = "parallel"))]
+        let _ = parallel;
+
+        // Start from empty tables so stale lengths are never reused
+        self.geometry_data.clear();
+        self.triangulation.chunks(3).enumerate().for_each(|(index, tri_idx)| {
+            self.geometry_data.add_triangle(index, &self.points, tri_idx, types, self.metric);
+        });
+    }
+
+    // Non-manifold edges (see check_manifold) are treated as walls: a void never
+    // grows across an edge shared by more than two triangles, nor across a barrier.
+    // Fails when the triangulation was preprocessed without areas (types 1).
+    pub fn delfin(
+        &self,
+        min_area: f32,
+        min_distance: f32,
+    ) -> Result<Vec<HashSet<usize>>, XenoError> {
+        if !self.triangulation.is_empty() && !self.geometry_data.triangles.iter().any(|t| t.area.is_some()) {
+            return Err(XenoError::AreasNotComputed);
+        }
+
+        let mut void_polygons: Vec<HashSet<usize>> = Vec::new();
+        // Masked triangles count as processed so they are neither seeds nor neighbors
+        let mut processed_triangles: HashSet<usize> = self.void_mask.clone();
+    
+        // Create a sorted list of triangles by their terminal edge length that meet the minimum distance criteria.
+        let mut triangles_sorted: Vec<(usize, f32)> = self.geometry_data.triangles.iter()
+            .filter_map(|t| t.terminal_edge.and_then(|e| self.geometry_data.edge_lengths.get(&e).map(|&l| (t.index, l))))
+            .filter(|&(_, length)| length >= min_distance)
+            .collect();
+    
+        // Sort by longest edge first
+        triangles_sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    
+        // Iterate through triangles starting from the one with the longest terminal edge
+        for (triangle_index, _) in triangles_sorted {
+
+            // Skip if already processed
+            if processed_triangles.contains(&triangle_index) {
+                continue;
+            }
+            
+            let mut edges_to_expand: HashSet<Edge> = HashSet::new();
+            let mut current_set: HashSet<usize> = HashSet::new();
+            
+            // Seed the initial set and edges to expand
+            current_set.insert(triangle_index);
+            processed_triangles.insert(triangle_index);
 
             // Get all edges of the current triangle
             if let Some(edges) = self.geometry_data.triangles.get(triangle_index).map(|t| t.get_edges()) {
-                for edge in edges {
+                for &edge in edges {
                     
                     // Add all edges to check for neighbors to expand
                     edges_to_expand.insert(edge);
@@ -355,9 +1343,12 @@ impl Xenobalanus {
             // Expand the set
             while let Some(edge) = edges_to_expand.iter().next().cloned() {
                 edges_to_expand.remove(&edge);
+                if self.barriers.contains(&edge) {
+                    continue;
+                }
     
                 // Get neighbor triangles for this edge
-                if let Some(neighbor_triangles) = self.geometry_data.edge_to_triangles.get(&edge) {
+                if let Some(neighbor_triangles) = self.geometry_data.edge_to_triangles.get(&edge).filter(|t| t.len() <= 2) {
 
                     // Iterate through neighbors
                     for &neighbor_index in neighbor_triangles {
@@ -380,7 +1371,7 @@ impl Xenobalanus {
                                     processed_triangles.insert(neighbor_index);
 
                                     // Add new neighbor edges to search
-                                    neighbor_triangle.get_edges().into_iter().for_each(|e| { edges_to_expand.insert(e); });
+                                    neighbor_triangle.get_edges().iter().for_each(|&e| { edges_to_expand.insert(e); });
                                 }
                             }
                         }
@@ -388,37 +1379,361 @@ impl Xenobalanus {
                 }
             }
             
-            // Add the expanded set if more than one triangle
-            if current_set.len() > 1 {
+            // Add the expanded set if it has enough triangles
+            if current_set.len() >= self.min_triangles {
                 void_polygons.push(current_set);
             }
         }
         
         // Retain only those sets that meet the minimum area criteria
-        void_polygons.retain(|set| {
-            set.iter()
-               .filter_map(|&i| self.geometry_data.triangles[i].area)
-               .sum::<f32>() >= min_area
-        });
+        void_polygons.retain(|set| self.void_area(set) >= min_area);
     
-        void_polygons
+        Ok(void_polygons)
     }    
 
+    // Threshold is the given percentile (0-100) of all triangle areas
+    pub fn delfin_percentile(&self, area_percentile: f32, min_distance: f32) -> Result<Vec<HashSet<usize>>, XenoError> {
+        let mut areas: Vec<f32> = self.geometry_data.triangles.iter()
+            .filter_map(|t| t.area)
+            .collect();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let min_area = if areas.is_empty() {
+            0.0
+        } else {
+            let rank = (area_percentile.clamp(0.0, 100.0) / 100.0 * (areas.len() - 1) as f32).round() as usize;
+            areas[rank]
+        };
+        self.delfin(min_area, min_distance)
+    }
+
+    // A void qualifies when its area times the local density at its centroid,
+    // i.e. the number of points expected to fill it, reaches min_count
+    pub fn delfin_adaptive(
+        &self,
+        density_fn: impl Fn(Point) -> f32,
+        min_count: f32,
+        min_distance: f32,
+    ) -> Result<Vec<HashSet<usize>>, XenoError> {
+        let mut void_polygons = self.delfin(0.0, min_distance)?;
+        void_polygons.retain(|set| self.void_area(set) * density_fn(self.void_centroid(set)) >= min_count);
+        Ok(void_polygons)
+    }
+
+    // Length of the shortest Delaunay edge at each point, infinite for isolated points
+    pub fn nearest_neighbor_distances(&self) -> Vec<f32> {
+        let mut distances = vec![f32::INFINITY; self.points.len()];
+        for (edge, &length) in &self.geometry_data.edge_lengths {
+            distances[edge.0] = distances[edge.0].min(length);
+            distances[edge.1] = distances[edge.1].min(length);
+        }
+        distances
+    }
+
+    // Area-weighted centroid of the void's triangles
+    pub fn void_centroid(&self, void: &HashSet<usize>) -> Point {
+        let (mut sum_x, mut sum_y, mut total) = (0.0, 0.0, 0.0);
+        for &i in void {
+            let triangle = &self.geometry_data.triangles[i];
+            let weight = triangle.area.unwrap_or(0.0);
+            let centroid = self.triangle_centroid(triangle);
+            sum_x += centroid.x * weight;
+            sum_y += centroid.y * weight;
+            total += weight;
+        }
+        if total > 0.0 {
+            Point::new(sum_x / total, sum_y / total)
+        } else {
+            Point::new(f32::NAN, f32::NAN)
+        }
+    }
+
+    fn triangle_centroid(&self, triangle: &TriangleData) -> Point {
+        let (x, y) = triangle.vertices.iter()
+            .fold((0.0, 0.0), |(x, y), &v| (x + self.points[v].x, y + self.points[v].y));
+        Point::new(x / 3.0, y / 3.0)
+    }
+
+    pub fn void_area(&self, void: &HashSet<usize>) -> f32 {
+        void.iter()
+            .filter_map(|&i| self.geometry_data.triangles[i].area)
+            .sum()
+    }
+
+    // Closed vertex rings (first vertex not repeated) formed by the edges
+    // that only one triangle of the void touches. Rings meeting at a pinch
+    // vertex are kept apart, so each ring is simple.
+    pub fn void_boundary(&self, void: &HashSet<usize>) -> Vec<Vec<usize>> {
+        let mut edge_counts: HashMap<Edge, usize> = HashMap::new();
+        for &i in void {
+            for &edge in self.geometry_data.triangles[i].get_edges() {
+                *edge_counts.entry(edge).or_default() += 1;
+            }
+        }
+        // Directed with the triangle, and so the void, on the left
+        let mut boundary: Vec<(usize, usize)> = Vec::new();
+        for &i in void {
+            let &[a, b, c] = &self.geometry_data.triangles[i].vertices[..] else { continue };
+            let ccw = Point::signed_area(self.points[a], self.points[b], self.points[c]) >= 0.0;
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                if edge_counts.get(&Edge(min(from, to), max(from, to))) == Some(&1) {
+                    boundary.push(if ccw { (from, to) } else { (to, from) });
+                }
+            }
+        }
+        trace_rings(&self.points, boundary)
+    }
+
+    pub fn void_polygon_with_holes(&self, void: &HashSet<usize>) -> Polygon<f32> {
+        let rings = self.void_boundary(void);
+        let exterior_index = match self.exterior_ring_index(&rings) {
+            Some(index) => index,
+            None => return Polygon::new(LineString::new(Vec::new()), Vec::new()),
+        };
+        let mut rings: Vec<LineString<f32>> = rings.iter()
+            .map(|ring| self.ring_linestring(ring))
+            .collect();
+        let exterior = rings.swap_remove(exterior_index);
+        Polygon::new(exterior, rings)
+    }
+
+    pub fn convex_hull(&self) -> Polygon<f32> {
+        let points: MultiPoint<f32> = self.points.iter()
+            .map(|p| GeoPoint::new(p.x, p.y))
+            .collect();
+        points.convex_hull()
+    }
+
+    // Void outline intersected with the convex hull of the points, largest piece if it splits
+    pub fn clip_void_to_hull(&self, void: &HashSet<usize>) -> Polygon<f32> {
+        self.clip_void_to_domain(void, &self.convex_hull()).into_iter()
+            .max_by(|a, b| a.unsigned_area().total_cmp(&b.unsigned_area()))
+            .unwrap_or_else(|| Polygon::new(LineString::new(Vec::new()), Vec::new()))
+    }
+
+    // Void outline intersected with the sampled domain, every piece if it splits.
+    // A void's triangles never leave the convex hull of the points, so this is where
+    // the real sampling region, e.g. a survey footprint or concave hull, trims it.
+    pub fn clip_void_to_domain(&self, void: &HashSet<usize>, domain: &Polygon<f32>) -> MultiPolygon<f32> {
+        self.void_polygon_with_holes(void).intersection(domain)
+    }
+
+    // Alpha shape of a vertex subset: the subset is triangulated and triangles whose
+    // circumradius exceeds 1 / alpha are dropped, alpha <= 0 keeps the convex hull.
+    // Returns the counter-clockwise outer boundary of the largest remaining piece.
+    pub fn concave_hull(&self, vertex_indices: Vec<usize>, alpha: f32) -> Vec<usize> {
+        let subset: Vec<DelaunatorPoint> = vertex_indices.iter()
+            .map(|&v| DelaunatorPoint { x: self.points[v].x as f64, y: self.points[v].y as f64 })
+            .collect();
+        let max_radius = if alpha > 0.0 { 1.0 / alpha as f64 } else { f64::INFINITY };
+
+        let mut edge_count: HashMap<Edge, usize> = HashMap::new();
+        for t in triangulate(&subset).triangles.chunks(3) {
+            let [a, b, c] = [vertex_indices[t[0]], vertex_indices[t[1]], vertex_indices[t[2]]];
+            let keep = circumcircle_f64(self.points[a], self.points[b], self.points[c])
+                .is_some_and(|(_, _, r2)| r2.sqrt() <= max_radius);
+            if keep {
+                for (u, v) in [(a, b), (b, c), (c, a)] {
+                    *edge_count.entry(Edge(min(u, v), max(u, v))).or_default() += 1;
+                }
+            }
+        }
+
+        let boundary: Vec<Edge> = edge_count.into_iter()
+            .filter(|&(_, count)| count == 1)
+            .map(|(edge, _)| edge)
+            .collect();
+        let rings = chain_rings(boundary);
+        let mut ring = match self.exterior_ring_index(&rings) {
+            Some(index) => rings[index].clone(),
+            None => return Vec::new(),
+        };
+        if self.ring_signed_area(&ring) < 0.0 {
+            ring.reverse();
+        }
+        ring
+    }
+
+    // Shoelace area inside the concave hull, pinched or crossing outlines are rejected
+    pub fn concave_hull_area(&self, vertex_indices: Vec<usize>, alpha: f32) -> Result<f32, XenoError> {
+        if let Some(&vertex) = vertex_indices.iter().find(|&&v| v >= self.points.len()) {
+            return Err(XenoError::VertexOutOfRange { vertex, points: self.points.len() });
+        }
+        let ring = self.concave_hull(vertex_indices, alpha);
+        if ring.is_empty() {
+            return Ok(0.0);
+        }
+        if !self.is_simple_polygon(&ring) {
+            return Err(XenoError::SelfIntersectingHull);
+        }
+        Ok(self.ring_signed_area(&ring).abs())
+    }
+
+    // Area of the void that falls inside an external mask polygon
+    pub fn void_polygon_overlap(&self, void: &HashSet<usize>, mask: &Polygon<f32>) -> f32 {
+        self.void_polygon_with_holes(void).intersection(mask).unsigned_area()
+    }
+
+    // The ring enclosing the largest area is the exterior, any others are holes
+    fn exterior_ring_index(&self, rings: &[Vec<usize>]) -> Option<usize> {
+        rings.iter()
+            .map(|ring| Polygon::new(self.ring_linestring(ring), Vec::new()).unsigned_area())
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(index, _)| index)
+    }
+
+    // Signed area of a vertex ring, positive when counter-clockwise
+    fn ring_signed_area(&self, ring: &[usize]) -> f32 {
+        // Fan of triangles from the first vertex
+        (1..ring.len().saturating_sub(1))
+            .map(|i| Point::signed_area(self.points[ring[0]], self.points[ring[i]], self.points[ring[i + 1]]))
+            .sum()
+    }
+
+    fn ring_linestring(&self, ring: &[usize]) -> LineString<f32> {
+        ring.iter().map(|&i| Coord::from(self.points[i])).collect()
+    }
+
+    pub fn circumcircle(&self, index: usize) -> Option<(Point, f32)> {
+        let v = &self.geometry_data.triangles.get(index)?.vertices;
+        if v.len() < 3 {
+            return None;
+        }
+        let (cx, cy, r2) = circumcircle_f64(self.points[v[0]], self.points[v[1]], self.points[v[2]])?;
+        Some((Point::new(cx as f32, cy as f32), r2.sqrt() as f32))
+    }
+
+    // Voronoi edges joining circumcenters of adjacent void triangles, kept
+    // when both circumcenters lie inside the void
+    pub fn medial_axis(&self, void: &HashSet<usize>) -> Vec<(Point, Point)> {
+        let polygon = self.void_polygon_with_holes(void);
+        let inside = |p: Point| polygon.intersects(&GeoPoint::new(p.x, p.y));
+
+        let mut edges: Vec<(Edge, usize, usize)> = Vec::new();
+        for &i in void {
+            for &edge in self.geometry_data.triangles[i].get_edges() {
+                if let Some(neighbors) = self.geometry_data.edge_to_triangles.get(&edge) {
+                    for &j in neighbors {
+                        if j > i && void.contains(&j) {
+                            edges.push((edge, i, j));
+                        }
+                    }
+                }
+            }
+        }
+        edges.sort_unstable_by_key(|&(e, i, j)| (e.0, e.1, i, j));
+
+        edges.into_iter()
+            .filter_map(|(_, i, j)| Some((self.circumcircle(i)?.0, self.circumcircle(j)?.0)))
+            .filter(|&(a, b)| inside(a) && inside(b))
+            .collect()
+    }
+
+    // Total length of all boundary rings, holes included
+    pub fn void_perimeter(&self, void: &HashSet<usize>) -> f32 {
+        self.void_boundary(void).iter()
+            .map(|ring| (0..ring.len())
+                .map(|i| self.points[ring[i]].distance(self.points[ring[(i + 1) % ring.len()]]))
+                .sum::<f32>())
+            .sum()
+    }
+
+    // Isoperimetric quotient 4 pi A / P^2, 1 for a circle
+    pub fn void_compactness(&self, voids: &[HashSet<usize>]) -> Vec<f32> {
+        voids.iter()
+            .map(|void| {
+                let perimeter = self.void_perimeter(void);
+                if perimeter > 0.0 {
+                    4.0 * std::f32::consts::PI * self.void_area(void) / perimeter.powi(2)
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    pub fn largest_void(&self, min_distance: f32) -> Result<Option<HashSet<usize>>, XenoError> {
+        Ok(self.delfin(0.0, min_distance)?
+            .into_iter()
+            .map(|void| (self.void_area(&void), void))
+            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+            .map(|(_, void)| void))
+    }
+
+    pub fn void_neighbors(&self, void: &HashSet<usize>, all_voids: &[HashSet<usize>]) -> Vec<usize> {
+        let void_edges: HashSet<Edge> = void.iter()
+            .flat_map(|&i| self.geometry_data.triangles[i].get_edges().iter().copied())
+            .collect();
+
+        // Neighboring voids share at least one triangle edge with the given void
+        all_voids.iter().enumerate()
+            .filter(|(_, other)| *other != void)
+            .filter(|(_, other)| other.iter().any(|&i| {
+                self.geometry_data.triangles[i].get_edges().iter().any(|e| void_edges.contains(e))
+            }))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
     pub fn dtscan(
         &self,
         min_pts: usize,
         max_closeness: f32,
+    ) -> Vec<Vec<usize>> {
+        self.dtscan_where(min_pts, max_closeness, |_| true, |edge| self.geometry_data.edge_lengths.get(&edge).copied())
+    }
+
+    pub fn dtscan_session(&self) -> DtscanSession {
+        DtscanSession::new(&self.geometry_data)
+    }
+
+    pub fn dtscan_in_region(
+        &self,
+        region: Polygon<f32>,
+        min_pts: usize,
+        max_closeness: f32,
+    ) -> Vec<Vec<usize>> {
+        self.dtscan_where(min_pts, max_closeness, |vertex| {
+            let point = self.points[vertex];
+            region.contains(&GeoPoint::new(point.x, point.y))
+        }, |edge| self.geometry_data.edge_lengths.get(&edge).copied())
+    }
+
+    // Edges are measured as sqrt((dx / sx)^2 + (dy / sy)^2), so a larger scale
+    // along one axis makes points spread along it count as closer
+    pub fn dtscan_anisotropic(
+        &self,
+        min_pts: usize,
+        max_closeness: f32,
+        scale: (f32, f32),
+    ) -> Vec<Vec<usize>> {
+        self.dtscan_where(min_pts, max_closeness, |_| true, |edge| {
+            let (a, b) = (self.points[edge.0], self.points[edge.1]);
+            Some(((a.x - b.x) / scale.0).hypot((a.y - b.y) / scale.1))
+        })
+    }
+
+    // Seeds and expansion are limited to vertices accepted by `include`,
+    // while core checks still use the full-field connectivity.
+    // `length` measures an edge, None excludes it.
+    fn dtscan_where(
+        &self,
+        min_pts: usize,
+        max_closeness: f32,
+        include: impl Fn(usize) -> bool,
+        length: impl Fn(Edge) -> Option<f32>,
     ) -> Vec<Vec<usize>> {
         let mut clusters: Vec<Vec<usize>> = Vec::new();
         let mut visited: HashSet<usize> = HashSet::new();
     
         for (&vertex_idx, neighbors) in &self.geometry_data.vertex_connections {
-            if visited.contains(&vertex_idx) {
+            if visited.contains(&vertex_idx) || !include(vertex_idx) {
                 continue;
             }
             // Check if vertex is a core vertex based on the number of connections and edge lengths
             if neighbors.len() >= min_pts && neighbors.iter().all(|&n| {
-                if let Some(&length) = self.geometry_data.edge_lengths.get(&Edge(min(vertex_idx, n), max(vertex_idx, n))) {
+                if let Some(length) = length(Edge(min(vertex_idx, n), max(vertex_idx, n))) {
                     length <= max_closeness
                 } else {
                     false
@@ -435,15 +1750,15 @@ impl Xenobalanus {
                     cluster.push(current_vertex);
     
                     // Add neighbors that are within max_closeness to to_expand
-                    self.geometry_data.vertex_connections.get(&current_vertex).map(|neighbors: &HashSet<usize>| {
+                    if let Some(neighbors) = self.geometry_data.vertex_connections.get(&current_vertex) {
                         for &neighbor in neighbors {
-                            if let Some(&length) = self.geometry_data.edge_lengths.get(&Edge(min(current_vertex, neighbor), max(current_vertex, neighbor))) {
-                                if length <= max_closeness && !visited.contains(&neighbor) {
+                            if let Some(length) = length(Edge(min(current_vertex, neighbor), max(current_vertex, neighbor))) {
+                                if length <= max_closeness && !visited.contains(&neighbor) && include(neighbor) {
                                     to_expand.push(neighbor);
                                 }
                             }
                         }
-                    });
+                    }
                 }
     
                 if !cluster.is_empty() {
@@ -454,4 +1769,3868 @@ impl Xenobalanus {
     
         clusters
     }
-}
\ No newline at end of file
+
+    // scikit-learn style labels, None marks noise
+    pub fn cluster_labels(&self, clusters: &[Vec<usize>]) -> Vec<Option<usize>> {
+        let mut labels: Vec<Option<usize>> = vec![None; self.points.len()];
+        for (cluster_id, cluster) in clusters.iter().enumerate() {
+            for &vertex in cluster {
+                labels[vertex] = Some(cluster_id);
+            }
+        }
+        labels
+    }
+
+    // Checks a vertex ring (closing edge implied) for self-intersections
+    pub fn is_simple_polygon(&self, vertices: &[usize]) -> bool {
+        let ring = match vertices {
+            [first, rest @ .., last] if first == last => &vertices[..rest.len() + 1],
+            _ => vertices,
+        };
+        let n = ring.len();
+        if n < 3 || ring.iter().collect::<HashSet<_>>().len() != n {
+            return false;
+        }
+
+        for i in 0..n {
+            let (a1, a2) = (self.points[ring[i]], self.points[ring[(i + 1) % n]]);
+            for j in (i + 1)..n {
+                // Adjacent segments share an endpoint by construction
+                if j == i + 1 || (i == 0 && j == n - 1) {
+                    continue;
+                }
+                let (b1, b2) = (self.points[ring[j]], self.points[ring[(j + 1) % n]]);
+                if segments_intersect(a1, a2, b1, b2) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    // V - E + F of the preprocessed triangulation, 1 for a disk
+    pub fn euler_characteristic(&self) -> i64 {
+        // V, E and F all come from the triangulation so the counts agree
+        let vertices: HashSet<usize> = self.triangulation.iter().copied().collect();
+        let edges: HashSet<Edge> = self.triangulation.chunks(3)
+            .flat_map(triangle_edges)
+            .map(|(a, b)| Edge(min(a, b), max(a, b)))
+            .collect();
+        let faces = self.triangulation.len() / 3;
+        vertices.len() as i64 - edges.len() as i64 + faces as i64
+    }
+
+    // Connected components and holes (b0, b1) of the triangulated surface
+    pub fn betti_numbers(&self) -> (usize, usize) {
+        let mut parent: Vec<usize> = (0..self.points.len()).collect();
+        fn find(parent: &mut [usize], v: usize) -> usize {
+            let mut root = v;
+            while parent[root] != root {
+                root = parent[root];
+            }
+            let mut current = v;
+            while parent[current] != root {
+                let next = parent[current];
+                parent[current] = root;
+                current = next;
+            }
+            root
+        }
+        for t in self.triangulation.chunks(3) {
+            for (a, b) in triangle_edges(t) {
+                let (a, b) = (find(&mut parent, a), find(&mut parent, b));
+                if a != b {
+                    parent[a] = b;
+                }
+            }
+        }
+        let vertices: HashSet<usize> = self.triangulation.iter().copied().collect();
+        let components = vertices.into_iter()
+            .map(|v| find(&mut parent, v))
+            .collect::<HashSet<usize>>()
+            .len();
+
+        // For a planar surface the Euler characteristic is b0 - b1
+        let holes = (components as i64 - self.euler_characteristic()).max(0) as usize;
+        (components, holes)
+    }
+
+    // Undirected edge orientations binned over 0-180 degrees
+    pub fn edge_bearing_histogram(&self, bins: usize) -> Vec<usize> {
+        let mut histogram = vec![0; bins];
+        if bins == 0 {
+            return histogram;
+        }
+        let bin_width = 180.0 / bins as f32;
+        for edge in self.geometry_data.edge_lengths.keys() {
+            let bearing = self.points[edge.0].bearing(self.points[edge.1]) % 180.0;
+            let bin = ((bearing / bin_width) as usize).min(bins - 1);
+            histogram[bin] += 1;
+        }
+        histogram
+    }
+
+    pub fn cluster_shape(&self, cluster: &[usize]) -> ShapeMetrics {
+        let n = cluster.len().max(1) as f32;
+        let (sum_x, sum_y) = cluster.iter()
+            .fold((0.0, 0.0), |(x, y), &v| (x + self.points[v].x, y + self.points[v].y));
+        let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+
+        let (mut cxx, mut cyy, mut cxy) = (0.0, 0.0, 0.0);
+        for &v in cluster {
+            let dx = self.points[v].x - mean_x;
+            let dy = self.points[v].y - mean_y;
+            cxx += dx * dx;
+            cyy += dy * dy;
+            cxy += dx * dy;
+        }
+        let (cxx, cyy, cxy) = (cxx / n, cyy / n, cxy / n);
+
+        // Eigenvalues of the symmetric 2x2 covariance matrix
+        let half_trace = (cxx + cyy) / 2.0;
+        let root = (((cxx - cyy) / 2.0).powi(2) + cxy * cxy).sqrt();
+        let major = half_trace + root;
+        let minor = (half_trace - root).max(0.0);
+
+        let elongation = if minor > 0.0 { major / minor } else if major > 0.0 { f32::INFINITY } else { 1.0 };
+        let compactness = if major > 0.0 { (minor / major).sqrt() } else { 1.0 };
+        let angle = (0.5 * (2.0 * cxy).atan2(cxx - cyy)).to_degrees();
+
+        ShapeMetrics {
+            elongation,
+            compactness,
+            principal_axis_bearing: (angle + 180.0) % 180.0,
+        }
+    }
+
+    // Constant gradient of the linearly interpolated scalar field on a triangle
+    pub fn scalar_gradient(&self, index: usize) -> Result<(f32, f32), XenoError> {
+        if self.scalars.len() != self.points.len() {
+            return Err(XenoError::ScalarsMismatch { scalars: self.scalars.len(), points: self.points.len() });
+        }
+        let tri = &self.triangulation[index * 3..index * 3 + 3];
+        let (p1, p2, p3) = (self.points[tri[0]], self.points[tri[1]], self.points[tri[2]]);
+        let (f1, f2, f3) = (self.scalars[tri[0]], self.scalars[tri[1]], self.scalars[tri[2]]);
+
+        let double_area = 2.0 * Point::signed_area(p1, p2, p3);
+        if double_area == 0.0 {
+            return Ok((f32::NAN, f32::NAN));
+        }
+        let gx = ((f2 - f1) * (p3.y - p1.y) - (f3 - f1) * (p2.y - p1.y)) / double_area;
+        let gy = ((f3 - f1) * (p2.x - p1.x) - (f2 - f1) * (p3.x - p1.x)) / double_area;
+        Ok((gx, gy))
+    }
+
+    // Peels needle triangles off the outline, call preprocess again afterwards
+    pub fn trim_boundary(&mut self, max_aspect: f32) {
+        let mut triangles: Vec<[usize; 3]> = self.triangulation.chunks(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect();
+        let edge_of = |a: usize, b: usize| Edge(min(a, b), max(a, b));
+
+        loop {
+            let mut edge_counts: HashMap<Edge, usize> = HashMap::new();
+            for t in &triangles {
+                for (a, b) in triangle_edges(t) {
+                    *edge_counts.entry(edge_of(a, b)).or_default() += 1;
+                }
+            }
+
+            let before = triangles.len();
+            triangles.retain(|t| {
+                let edges = triangle_edges(t);
+                let on_boundary = edges.iter().any(|&(a, b)| edge_counts[&edge_of(a, b)] == 1);
+                if !on_boundary {
+                    return true;
+                }
+                let lengths = edges.map(|(a, b)| self.points[a].distance(self.points[b]));
+                let longest = lengths.iter().cloned().fold(f32::MIN, f32::max);
+                let shortest = lengths.iter().cloned().fold(f32::MAX, f32::min);
+                longest <= max_aspect * shortest
+            });
+            if triangles.len() == before {
+                break;
+            }
+        }
+
+        self.triangulation = triangles.into_iter().flatten().collect();
+        self.halfedges.clear();
+        self.degenerate = self.triangulation.is_empty();
+        // Surviving triangles are renumbered, so masked indices would point elsewhere
+        self.void_mask.clear();
+        self.geometry_data = GeometryData::new();
+    }
+
+    // Layers: faint triangulation edges, filled voids, cluster points on top
+    // Coordinates are written with `precision` decimal places
+    pub fn to_svg(&self, voids: &[HashSet<usize>], clusters: &[Vec<usize>], width: u32, height: u32, precision: usize) -> String {
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+            width, height, width, height
+        );
+        let (lo, hi) = match self.bounding_box() {
+            Some(bounds) => bounds,
+            None => return svg + "</svg>\n",
+        };
+
+        // Uniform scale, y flipped since screen coordinates grow downwards
+        let scale = (width as f32 / (hi.x - lo.x).max(f32::EPSILON))
+            .min(height as f32 / (hi.y - lo.y).max(f32::EPSILON));
+        let project = |p: Point| ((p.x - lo.x) * scale, (hi.y - p.y) * scale);
+        // One closed subpath per ring
+        let ring_path = |ring: &[usize]| ring.iter()
+            .enumerate()
+            .map(|(i, &v)| {
+                let (x, y) = project(self.points[v]);
+                format!("{}{:.*},{:.*}", if i == 0 { "M" } else { "L" }, precision, x, precision, y)
+            })
+            .collect::<Vec<String>>()
+            .join(" ") + " Z";
+
+        svg.push_str("<g id=\"edges\" stroke=\"#999999\" stroke-width=\"0.5\" stroke-opacity=\"0.3\">\n");
+        let edges: HashSet<Edge> = self.triangulation.chunks(3)
+            .flat_map(triangle_edges)
+            .map(|(a, b)| Edge(min(a, b), max(a, b)))
+            .collect();
+        for edge in edges {
+            let (x1, y1) = project(self.points[edge.0]);
+            let (x2, y2) = project(self.points[edge.1]);
+            svg.push_str(&format!(
+                "<line x1=\"{:.*}\" y1=\"{:.*}\" x2=\"{:.*}\" y2=\"{:.*}\"/>\n",
+                precision, x1, precision, y1, precision, x2, precision, y2
+            ));
+        }
+        svg.push_str("</g>\n");
+
+        // One path per void, its holes cut out by the even-odd rule so the edges show through
+        svg.push_str("<g id=\"voids\" fill=\"#4477aa\" fill-opacity=\"0.6\" stroke=\"#224466\">\n");
+        for void in voids {
+            let rings: Vec<String> = self.void_rings_oriented(void).iter().map(|ring| ring_path(ring)).collect();
+            if !rings.is_empty() {
+                svg.push_str(&format!("<path fill-rule=\"evenodd\" d=\"{}\"/>\n", rings.join(" ")));
+            }
+        }
+        svg.push_str("</g>\n");
+
+        svg.push_str("<g id=\"clusters\" fill=\"#cc3311\">\n");
+        for cluster in clusters {
+            for &v in cluster {
+                let (x, y) = project(self.points[v]);
+                svg.push_str(&format!("<circle cx=\"{:.*}\" cy=\"{:.*}\" r=\"1.5\"/>\n", precision, x, precision, y));
+            }
+        }
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+
+    // CSR layout for FFI: members of void i are `flat[offsets[i]..offsets[i + 1]]`,
+    // sorted ascending, and `offsets` has one more entry than there are voids.
+    pub fn voids_flat(&self, voids: &[HashSet<usize>]) -> (Vec<usize>, Vec<usize>) {
+        let mut flat: Vec<usize> = Vec::with_capacity(voids.iter().map(|v| v.len()).sum());
+        let mut offsets: Vec<usize> = Vec::with_capacity(voids.len() + 1);
+        offsets.push(0);
+        for void in voids {
+            let start = flat.len();
+            flat.extend(void.iter().copied());
+            flat[start..].sort_unstable();
+            offsets.push(flat.len());
+        }
+        (flat, offsets)
+    }
+
+    // CSR layout for FFI: members of cluster i are `flat[offsets[i]..offsets[i + 1]]`,
+    // in their original order, and `offsets` has one more entry than there are clusters.
+    pub fn clusters_flat(&self, clusters: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+        let mut flat: Vec<usize> = Vec::with_capacity(clusters.iter().map(|c| c.len()).sum());
+        let mut offsets: Vec<usize> = Vec::with_capacity(clusters.len() + 1);
+        offsets.push(0);
+        for cluster in clusters {
+            flat.extend_from_slice(cluster);
+            offsets.push(flat.len());
+        }
+        (flat, offsets)
+    }
+
+    // Closest other void by centroid distance, (i, infinity) when void i has no neighbor
+    pub fn void_nearest_neighbor(&self, voids: &[HashSet<usize>]) -> Vec<(usize, f32)> {
+        let centroids: Vec<Point> = voids.iter().map(|v| self.void_centroid(v)).collect();
+        centroids.iter().enumerate()
+            .map(|(i, centroid)| {
+                centroids.iter().enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(j, other)| (j, centroid.distance(*other)))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .unwrap_or((i, f32::INFINITY))
+            })
+            .collect()
+    }
+
+    // Aggregate statistics of the current triangulation
+    pub fn mesh_report(&self) -> MeshReport {
+        let mut edge_counts: HashMap<Edge, usize> = HashMap::new();
+        let mut report = MeshReport {
+            n_points: self.points.len(),
+            n_triangles: self.triangulation.len() / 3,
+            n_edges: 0,
+            min_angle: f32::INFINITY,
+            max_angle: f32::NEG_INFINITY,
+            min_area: f32::INFINITY,
+            max_area: f32::NEG_INFINITY,
+            n_boundary_edges: 0,
+            n_sliver_triangles: 0,
+        };
+
+        for t in self.triangulation.chunks(3) {
+            for (a, b) in triangle_edges(t) {
+                *edge_counts.entry(Edge(min(a, b), max(a, b))).or_default() += 1;
+            }
+            let (a, b, c) = (self.points[t[0]], self.points[t[1]], self.points[t[2]]);
+            let angles = triangle_angles(a, b, c);
+            let smallest = angles.iter().cloned().fold(f32::INFINITY, f32::min);
+            let largest = angles.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            let area = Point::signed_area(a, b, c).abs();
+
+            report.min_angle = report.min_angle.min(smallest);
+            report.max_angle = report.max_angle.max(largest);
+            report.min_area = report.min_area.min(area);
+            report.max_area = report.max_area.max(area);
+            if smallest < SLIVER_ANGLE {
+                report.n_sliver_triangles += 1;
+            }
+        }
+
+        report.n_edges = edge_counts.len();
+        report.n_boundary_edges = edge_counts.values().filter(|&&count| count == 1).count();
+        report
+    }
+
+    // Streams a FeatureCollection with one Polygon feature per void, exterior
+    // rings counter-clockwise and holes clockwise as RFC 7946 recommends,
+    // with coordinates and areas written with `precision` decimal places
+    pub fn write_voids_geojson<W: Write>(&self, voids: &[HashSet<usize>], mut writer: W, precision: usize) -> Result<(), XenoError> {
+        writer.write_all(b"{\"type\":\"FeatureCollection\",\"features\":[")?;
+        for (id, void) in voids.iter().enumerate() {
+            if id > 0 {
+                writer.write_all(b",")?;
+            }
+            write!(writer, "{{\"type\":\"Feature\",\"properties\":{{\"id\":{},\"area\":{:.*}}},", id, precision, self.void_area(void))?;
+            writer.write_all(b"\"geometry\":{\"type\":\"Polygon\",\"coordinates\":[")?;
+            for (r, ring) in self.void_rings_oriented(void).iter().enumerate() {
+                if r > 0 {
+                    writer.write_all(b",")?;
+                }
+                writer.write_all(b"[")?;
+                for (k, &v) in ring.iter().chain(ring.first()).enumerate() {
+                    if k > 0 {
+                        writer.write_all(b",")?;
+                    }
+                    write!(writer, "[{:.*},{:.*}]", precision, self.points[v].x, precision, self.points[v].y)?;
+                }
+                writer.write_all(b"]")?;
+            }
+            writer.write_all(b"]}}")?;
+        }
+        writer.write_all(b"]}")?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    // Exterior ring first and counter-clockwise, holes clockwise
+    fn void_rings_oriented(&self, void: &HashSet<usize>) -> Vec<Vec<usize>> {
+        let mut rings = self.void_boundary(void);
+        if let Some(exterior) = self.exterior_ring_index(&rings) {
+            rings.swap(0, exterior);
+        }
+        for (r, ring) in rings.iter_mut().enumerate() {
+            if (r == 0) != (self.ring_signed_area(ring) > 0.0) {
+                ring.reverse();
+            }
+        }
+        rings
+    }
+
+    // Edges whose removal disconnects the connectivity graph restricted to edges no
+    // longer than max_length, as dtscan thresholds it (iterative Tarjan). Every edge
+    // of the full triangulation lies on a triangle, so only a threshold leaves bridges.
+    pub fn bridge_edges(&self, max_length: f32) -> Vec<Edge> {
+        let graph = &self.geometry_data.vertex_connections;
+        let lengths = &self.geometry_data.edge_lengths;
+        let mut vertices: Vec<usize> = graph.keys().copied().collect();
+        vertices.sort_unstable();
+
+        let mut discovery: HashMap<usize, usize> = HashMap::new();
+        let mut low: HashMap<usize, usize> = HashMap::new();
+        let mut bridges: Vec<Edge> = Vec::new();
+        let mut time = 0;
+
+        for &root in &vertices {
+            if discovery.contains_key(&root) {
+                continue;
+            }
+            discovery.insert(root, time);
+            low.insert(root, time);
+            time += 1;
+
+            // Stack of (vertex, parent, neighbors still to visit)
+            let neighbors = |v: usize| -> Vec<usize> {
+                graph.get(&v)
+                    .map(|n| n.iter()
+                        .copied()
+                        .filter(|&w| lengths.get(&Edge(min(v, w), max(v, w))).is_some_and(|&l| l <= max_length))
+                        .collect())
+                    .unwrap_or_default()
+            };
+            let mut stack: Vec<(usize, Option<usize>, Vec<usize>)> = vec![(root, None, neighbors(root))];
+            while let Some((vertex, parent, pending)) = stack.last_mut() {
+                let (vertex, parent) = (*vertex, *parent);
+                if let Some(next) = pending.pop() {
+                    if Some(next) == parent {
+                        continue;
+                    }
+                    if let Some(&seen) = discovery.get(&next) {
+                        let current = low[&vertex].min(seen);
+                        low.insert(vertex, current);
+                    } else {
+                        discovery.insert(next, time);
+                        low.insert(next, time);
+                        time += 1;
+                        stack.push((next, Some(vertex), neighbors(next)));
+                    }
+                } else {
+                    stack.pop();
+                    if let Some(parent) = parent {
+                        let child_low = low[&vertex];
+                        let current = low[&parent].min(child_low);
+                        low.insert(parent, current);
+                        if child_low > discovery[&parent] {
+                            bridges.push(Edge(min(parent, vertex), max(parent, vertex)));
+                        }
+                    }
+                }
+            }
+        }
+
+        bridges.sort_unstable_by_key(|e| (e.0, e.1));
+        bridges
+    }
+
+    // Index of the void with the closest centroid for each query, empty when there are no voids
+    pub fn assign_to_nearest_void(&self, queries: &[Point], voids: &[HashSet<usize>]) -> Vec<usize> {
+        if voids.is_empty() {
+            return Vec::new();
+        }
+        let centroids: Vec<Point> = voids.iter().map(|v| self.void_centroid(v)).collect();
+        #[cfg(feature = "parallel")]
+        let query_iter = queries.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let query_iter = queries.iter();
+
+        query_iter
+            .map(|query| {
+                centroids.iter().enumerate()
+                    .map(|(i, centroid)| (i, query.distance(*centroid)))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(i, _)| i)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    // Circumcenters of oversized triangles as refinement seeds, skipping
+    // those that fall outside the convex hull
+    pub fn refinement_candidates(&self, max_area: f32) -> Vec<Point> {
+        let hull = self.convex_hull();
+        self.geometry_data.triangles.iter()
+            .filter(|t| t.area.is_some_and(|area| area > max_area))
+            .filter_map(|t| self.circumcircle(t.index))
+            .map(|(center, _)| center)
+            .filter(|p| hull.intersects(&GeoPoint::new(p.x, p.y)))
+            .collect()
+    }
+
+    pub fn cluster_hull_area(&self, cluster: &[usize]) -> f32 {
+        let points: MultiPoint<f32> = cluster.iter()
+            .map(|&v| GeoPoint::new(self.points[v].x, self.points[v].y))
+            .collect();
+        points.convex_hull().unsigned_area()
+    }
+
+    // Members per unit hull area, infinite for clusters with a degenerate hull
+    pub fn cluster_density(&self, clusters: &[Vec<usize>]) -> Vec<f32> {
+        clusters.iter()
+            .map(|cluster| {
+                let area = self.cluster_hull_area(cluster);
+                if area > 0.0 { cluster.len() as f32 / area } else { f32::INFINITY }
+            })
+            .collect()
+    }
+
+    // Outer boundary of the triangulation as a counter-clockwise vertex ring
+    pub fn triangulation_boundary(&self) -> Vec<usize> {
+        let mut edge_counts: HashMap<Edge, usize> = HashMap::new();
+        for t in self.triangulation.chunks(3) {
+            for (a, b) in triangle_edges(t) {
+                *edge_counts.entry(Edge(min(a, b), max(a, b))).or_default() += 1;
+            }
+        }
+        let rings = chain_rings(edge_counts.into_iter()
+            .filter(|&(_, count)| count == 1)
+            .map(|(edge, _)| edge)
+            .collect());
+
+        let mut boundary = match self.exterior_ring_index(&rings) {
+            Some(index) => rings[index].clone(),
+            None => return Vec::new(),
+        };
+        if self.ring_signed_area(&boundary) < 0.0 {
+            boundary.reverse();
+        }
+        boundary
+    }
+
+    // Closed outline, the first point is repeated at the end
+    pub fn boundary_linestring(&self) -> LineString<f32> {
+        let boundary = self.triangulation_boundary();
+        boundary.iter()
+            .chain(boundary.first())
+            .map(|&v| Coord::from(self.points[v]))
+            .collect()
+    }
+
+    // Longest shortest path along Delaunay edges between two cluster members,
+    // walking only edges with both endpoints in the cluster
+    pub fn cluster_graph_diameter(&self, cluster: &[usize]) -> f32 {
+        let members: HashSet<usize> = cluster.iter().copied().collect();
+        let mut diameter: f32 = 0.0;
+
+        for &source in &members {
+            let mut distances: HashMap<usize, f32> = HashMap::new();
+            let mut heap: BinaryHeap<Reverse<(OrderedDistance, usize)>> = BinaryHeap::new();
+            distances.insert(source, 0.0);
+            heap.push(Reverse((OrderedDistance(0.0), source)));
+
+            while let Some(Reverse((OrderedDistance(distance), vertex))) = heap.pop() {
+                if distance > distances[&vertex] {
+                    continue;
+                }
+                diameter = diameter.max(distance);
+                let Some(neighbors) = self.geometry_data.vertex_connections.get(&vertex) else { continue };
+                for &neighbor in neighbors {
+                    if !members.contains(&neighbor) {
+                        continue;
+                    }
+                    let Some(&length) = self.geometry_data.edge_lengths.get(&Edge(min(vertex, neighbor), max(vertex, neighbor))) else { continue };
+                    let candidate = distance + length;
+                    if distances.get(&neighbor).is_none_or(|&known| candidate < known) {
+                        distances.insert(neighbor, candidate);
+                        heap.push(Reverse((OrderedDistance(candidate), neighbor)));
+                    }
+                }
+            }
+        }
+        diameter
+    }
+
+    // Refreshes triangle areas after points moved. Only valid while the
+    // triangulation connectivity is unchanged; edge lengths are not updated.
+    pub fn recompute_areas(&mut self) {
+        self.geometry_data.centroid_index = OnceLock::new();
+        for triangle in self.geometry_data.triangles.iter_mut() {
+            if triangle.area.is_none() {
+                continue;
+            }
+            // Same vertex order as preprocess so the results match exactly
+            if let [a, b, c] = self.triangulation[triangle.index * 3..triangle.index * 3 + 3] {
+                triangle.area = Some(triangle_area(self.points[a], self.points[b], self.points[c], self.metric));
+            }
+        }
+    }
+
+    // Null model for void significance: each trial draws a Poisson number of
+    // uniform points (mean = current count) in the current bounding box and
+    // records the largest void delfin finds with the given parameters
+    pub fn poisson_void_expectation(&self, n_trials: usize, seed: u64, min_area: f32, min_distance: f32) -> VoidStats {
+        let (lo, hi) = match self.bounding_box() {
+            Some(bounds) if n_trials > 0 => bounds,
+            _ => return VoidStats { mean_max_area: 0.0, std_max_area: 0.0, n_trials: 0 },
+        };
+        let count = Poisson::new(self.points.len().max(1) as f64).unwrap();
+
+        #[cfg(feature = "parallel")]
+        let trials = (0..n_trials).into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let trials = 0..n_trials;
+
+        let maxima: Vec<f32> = trials
+            .map(|trial| {
+                let mut rng = StdRng::seed_from_u64(seed.wrapping_add(trial as u64));
+                let n = count.sample(&mut rng) as usize;
+                let mut xeno = Xenobalanus::with_capacity(n);
+                xeno.metric = self.metric;
+                xeno.min_triangles = self.min_triangles;
+                xeno.points.extend((0..n).map(|_| Point::new(rng.gen_range(lo.x..=hi.x), rng.gen_range(lo.y..=hi.y))));
+                xeno.delaunay();
+                xeno.preprocess(2, false);
+                xeno.delfin(min_area, min_distance).unwrap_or_default().into_iter()
+                    .map(|void| {
+                        // Summed in index order, as a HashSet's iteration order differs between runs
+                        let mut triangles: Vec<usize> = void.into_iter().collect();
+                        triangles.sort_unstable();
+                        triangles.iter().filter_map(|&i| xeno.geometry_data.triangles[i].area).sum::<f32>()
+                    })
+                    .fold(0.0, f32::max)
+            })
+            .collect();
+
+        let mean = maxima.iter().sum::<f32>() / n_trials as f32;
+        let variance = maxima.iter().map(|m| (m - mean).powi(2)).sum::<f32>() / n_trials as f32;
+        VoidStats { mean_max_area: mean, std_max_area: variance.sqrt(), n_trials }
+    }
+
+    // Signed turning angle in degrees at each vertex of a closed ring,
+    // positive for left (counter-clockwise) turns
+    pub fn boundary_curvature(&self, ordered_vertices: &[usize]) -> Vec<f32> {
+        let n = ordered_vertices.len();
+        (0..n)
+            .map(|i| {
+                let prev = self.points[ordered_vertices[(i + n - 1) % n]];
+                let current = self.points[ordered_vertices[i]];
+                let next = self.points[ordered_vertices[(i + 1) % n]];
+                let turn = current.bearing(next) - prev.bearing(current);
+                let turn = (turn + 540.0) % 360.0 - 180.0;
+                if turn == -180.0 { 180.0 } else { turn }
+            })
+            .collect()
+    }
+
+    // Lower bound on the edge flips turning this triangulation into the other:
+    // each flip replaces one edge, so every edge only one side has needs one
+    pub fn flip_distance(&self, other_triangles: &[usize]) -> usize {
+        let edge_set = |triangles: &[usize]| -> HashSet<Edge> {
+            triangles.chunks(3)
+                .flat_map(triangle_edges)
+                .map(|(a, b)| Edge(min(a, b), max(a, b)))
+                .collect()
+        };
+        let ours = edge_set(&self.triangulation);
+        let theirs = edge_set(other_triangles);
+        ours.difference(&theirs).count().max(theirs.difference(&ours).count())
+    }
+
+    // Estimated heap usage of the preprocessed geometry: vector capacities plus
+    // hash table buckets with their control bytes, nested collections included
+    pub fn geometry_memory_bytes(&self) -> usize {
+        let data = &self.geometry_data;
+        let triangles = data.triangles.capacity() * std::mem::size_of::<TriangleData>()
+            + data.triangles.iter().map(|t| t.vertices.capacity() * std::mem::size_of::<usize>()).sum::<usize>();
+        let edge_to_triangles = hash_table_bytes::<(Edge, Vec<usize>)>(data.edge_to_triangles.capacity())
+            + data.edge_to_triangles.values().map(|t| t.capacity() * std::mem::size_of::<usize>()).sum::<usize>();
+        let edge_lengths = hash_table_bytes::<(Edge, f32)>(data.edge_lengths.capacity());
+        let vertex_connections = hash_table_bytes::<(usize, HashSet<usize>)>(data.vertex_connections.capacity())
+            + data.vertex_connections.values().map(|c| hash_table_bytes::<usize>(c.capacity())).sum::<usize>();
+        let vertex_edges = hash_table_bytes::<(usize, Vec<Edge>)>(data.vertex_edges.capacity())
+            + data.vertex_edges.values().map(|e| e.capacity() * std::mem::size_of::<Edge>()).sum::<usize>();
+        triangles + edge_to_triangles + edge_lengths + vertex_connections + vertex_edges
+    }
+
+    // Cell coordinates are written with `precision` decimal places
+    pub fn voronoi_to_geojson(&self, precision: usize) -> String {
+        let cells = match self.bounding_box() {
+            Some((lo, hi)) => self.voronoi_cells(lo, hi),
+            None => Vec::new(),
+        };
+        let features: Vec<String> = cells.iter().enumerate()
+            .map(|(site, cell)| {
+                let coordinates: Vec<String> = cell.iter()
+                    .chain(cell.first())
+                    .map(|(x, y)| format!("[{:.*},{:.*}]", precision, x, precision, y))
+                    .collect();
+                format!(
+                    "{{\"type\":\"Feature\",\"properties\":{{\"site\":{}}},\"geometry\":{{\"type\":\"Polygon\",\"coordinates\":[[{}]]}}}}",
+                    site, coordinates.join(",")
+                )
+            })
+            .collect();
+        format!("{{\"type\":\"FeatureCollection\",\"features\":[{}]}}", features.join(","))
+    }
+
+    // Each site's cell is the lo-hi box cut by the bisectors towards its
+    // Delaunay neighbors, which bounds the open cells along the hull
+    fn voronoi_cells(&self, lo: Point, hi: Point) -> Vec<Vec<(f64, f64)>> {
+        let (lo, hi) = ((lo.x as f64, lo.y as f64), (hi.x as f64, hi.y as f64));
+        let mut neighbors: Vec<HashSet<usize>> = vec![HashSet::new(); self.points.len()];
+        for t in self.triangulation.chunks(3) {
+            for (a, b) in triangle_edges(t) {
+                neighbors[a].insert(b);
+                neighbors[b].insert(a);
+            }
+        }
+
+        self.points.iter().enumerate()
+            .map(|(site, p)| {
+                let mut cell = vec![lo, (hi.0, lo.1), hi, (lo.0, hi.1)];
+                for &n in &neighbors[site] {
+                    let q = self.points[n];
+                    let normal = ((q.x - p.x) as f64, (q.y - p.y) as f64);
+                    let midpoint = (((p.x + q.x) / 2.0) as f64, ((p.y + q.y) / 2.0) as f64);
+                    cell = clip_half_plane(&cell, midpoint, normal);
+                }
+                cell
+            })
+            .collect()
+    }
+
+    // Moves every point to the centroid of its bounding-box-clipped Voronoi cell
+    pub fn lloyd_relax(&mut self, iterations: usize) {
+        self.lloyd_relax_weighted(iterations, |_| 1.0)
+    }
+
+    // Cells are fan-split and every fan triangle is sampled at the centroids of a
+    // 4x4 subdivision, so points drift towards where weight_fn is high. Cells are
+    // clipped to the starting bounding box, so the domain does not shrink.
+    pub fn lloyd_relax_weighted(&mut self, iterations: usize, weight_fn: impl Fn(Point) -> f32) {
+        const SUBDIVISIONS: usize = 4;
+        let k = SUBDIVISIONS as f64;
+        let (lo, hi) = match self.bounding_box() {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        for _ in 0..iterations {
+            self.delaunay();
+            let cells = self.voronoi_cells(lo, hi);
+            for (point, cell) in self.points.iter_mut().zip(cells) {
+                let (mut sum_x, mut sum_y, mut total) = (0.0, 0.0, 0.0);
+                for i in 1..cell.len().saturating_sub(1) {
+                    let (a, b, c) = (cell[0], cell[i], cell[i + 1]);
+                    let area = ((b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1)).abs() / 2.0 / (k * k);
+                    let mut sample = |s: f64, t: f64| {
+                        let x = a.0 + s / k * (b.0 - a.0) + t / k * (c.0 - a.0);
+                        let y = a.1 + s / k * (b.1 - a.1) + t / k * (c.1 - a.1);
+                        let weight = area * weight_fn(Point::new(x as f32, y as f32)).max(0.0) as f64;
+                        sum_x += x * weight;
+                        sum_y += y * weight;
+                        total += weight;
+                    };
+                    for u in 0..SUBDIVISIONS {
+                        for v in 0..SUBDIVISIONS - u {
+                            sample(u as f64 + 1.0 / 3.0, v as f64 + 1.0 / 3.0);
+                            if u + v + 2 <= SUBDIVISIONS {
+                                sample(u as f64 + 2.0 / 3.0, v as f64 + 2.0 / 3.0);
+                            }
+                        }
+                    }
+                }
+                if total > 0.0 {
+                    *point = Point::new((sum_x / total) as f32, (sum_y / total) as f32);
+                }
+            }
+        }
+        self.invalidate();
+    }
+
+    // Edges shared by more than two triangles, only possible in imported meshes
+    pub fn check_manifold(&self) -> Vec<Edge> {
+        let mut edges: Vec<Edge> = self.geometry_data.edge_to_triangles.iter()
+            .filter(|(_, triangles)| triangles.len() > 2)
+            .map(|(&edge, _)| edge)
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    // Point with the longest shortest Delaunay edge, points without edges are ignored
+    pub fn most_isolated_point(&self) -> Option<usize> {
+        self.nearest_neighbor_distances().iter()
+            .enumerate()
+            .filter(|(_, distance)| distance.is_finite())
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(index, _)| index)
+    }
+
+    // Points every `spacing` along the closed ring, starting at its first vertex.
+    // Leftover distance carries over, so segments shorter than spacing are skipped over.
+    pub fn resample_boundary(&self, ordered_vertices: &[usize], spacing: f32) -> Vec<Point> {
+        let ring = match ordered_vertices {
+            [first, rest @ .., last] if first == last => &ordered_vertices[..rest.len() + 1],
+            _ => ordered_vertices,
+        };
+        if ring.len() < 2 || spacing <= 0.0 {
+            return ring.iter().map(|&v| self.points[v]).collect();
+        }
+
+        let mut samples: Vec<Point> = Vec::new();
+        let mut offset = 0.0;
+        for i in 0..ring.len() {
+            let (a, b) = (self.points[ring[i]], self.points[ring[(i + 1) % ring.len()]]);
+            let length = a.distance(b);
+            while offset < length {
+                let t = offset / length;
+                samples.push(Point::new(a.x + t * (b.x - a.x), a.y + t * (b.y - a.y)));
+                offset += spacing;
+            }
+            offset -= length;
+        }
+        samples
+    }
+
+    // Unordered point pairs per separation bin [bins[i], bins[i + 1]), Euclidean.
+    // Each point only range-queries the spatial index up to the outermost edge.
+    pub fn pair_counts(&self, bins: &[f32]) -> Vec<usize> {
+        let mut counts = vec![0; bins.len().saturating_sub(1)];
+        let (first, last) = match (bins.first(), bins.last()) {
+            (Some(&first), Some(&last)) if bins.len() > 1 => (first, last),
+            _ => return counts,
+        };
+        let index = self.point_index();
+        for (i, p) in self.points.iter().enumerate() {
+            for neighbor in index.locate_within_distance([p.x, p.y], last * last) {
+                if neighbor.data <= i {
+                    continue;
+                }
+                let distance = p.distance(self.points[neighbor.data]);
+                if distance < first || distance >= last {
+                    continue;
+                }
+                let bin = bins.partition_point(|&edge| edge <= distance) - 1;
+                if bin < counts.len() {
+                    counts[bin] += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    fn point_index(&self) -> RTree<GeomWithData<[f32; 2], usize>> {
+        RTree::bulk_load(self.points.iter()
+            .enumerate()
+            .map(|(i, p)| GeomWithData::new([p.x, p.y], i))
+            .collect())
+    }
+
+    // Contracts Delaunay edges shorter than min_length into their midpoints, shortest
+    // first and at most once per vertex per pass, re-triangulating between passes so
+    // the mesh can never fold over. Point indices change and the fresh triangulation
+    // has to be preprocessed again. A surviving point carries the mean of the two
+    // collapsed scalars.
+    pub fn collapse_short_edges(&mut self, min_length: f32) -> usize {
+        let mut collapses = 0;
+        loop {
+            self.invalidate();
+            self.delaunay();
+            let mut short: Vec<(f32, usize, usize)> = self.triangulation.chunks(3)
+                .flat_map(triangle_edges)
+                .filter(|&(a, b)| a < b)
+                .map(|(a, b)| (self.points[a].distance_metric(self.points[b], self.metric), a, b))
+                .filter(|&(length, _, _)| length < min_length)
+                .collect();
+            if short.is_empty() {
+                break;
+            }
+            short.sort_by(|x, y| x.0.total_cmp(&y.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+
+            let before = self.points.len();
+            let scalars_aligned = self.scalars.len() == before;
+            let mut removed = vec![false; before];
+            let mut touched = vec![false; before];
+            for (_, a, b) in short {
+                if touched[a] || touched[b] {
+                    continue;
+                }
+                touched[a] = true;
+                touched[b] = true;
+                removed[b] = true;
+                let (p, q) = (self.points[a], self.points[b]);
+                self.points[a] = Point::new((p.x + q.x) / 2.0, (p.y + q.y) / 2.0);
+                if scalars_aligned {
+                    self.scalars[a] = (self.scalars[a] + self.scalars[b]) / 2.0;
+                }
+                collapses += 1;
+            }
+            let kept: Vec<usize> = (0..before).filter(|&i| !removed[i]).collect();
+            self.points = kept.iter().map(|&i| self.points[i]).collect();
+            self.remap_scalars(&kept, before);
+        }
+        collapses
+    }
+
+    // Void membership wins, otherwise a triangle belongs to a cluster when all
+    // three vertices are members of that same cluster
+    pub fn segment_triangles(&self, voids: &[HashSet<usize>], clusters: &[Vec<usize>]) -> Vec<TriangleClass> {
+        let mut classes = vec![TriangleClass::Background; self.triangulation.len() / 3];
+        let labels = self.cluster_labels(clusters);
+        for (index, t) in self.triangulation.chunks(3).enumerate() {
+            if let Some(cluster) = labels[t[0]] {
+                if labels[t[1]] == Some(cluster) && labels[t[2]] == Some(cluster) {
+                    classes[index] = TriangleClass::Cluster(cluster);
+                }
+            }
+        }
+        for (id, void) in voids.iter().enumerate() {
+            for &index in void {
+                if index < classes.len() {
+                    classes[index] = TriangleClass::Void(id);
+                }
+            }
+        }
+        classes
+    }
+
+    // Preprocessed triangles whose centroid lies within r of p, queried through an
+    // R-tree over the centroids that is built once per preprocess
+    pub fn triangles_near(&self, p: Point, r: f32) -> Vec<usize> {
+        let index = self.geometry_data.centroid_index.get_or_init(|| {
+            RTree::bulk_load(self.iter_triangles()
+                .map(|(index, t)| {
+                    let centroid = self.triangle_centroid(t);
+                    GeomWithData::new([centroid.x, centroid.y], index)
+                })
+                .collect())
+        });
+        let mut near: Vec<usize> = index.locate_within_distance([p.x, p.y], r * r)
+            .map(|entry| entry.data)
+            .collect();
+        near.sort_unstable();
+        near
+    }
+
+    // Radius at which each edge enters the alpha complex: half its length when its
+    // diametral circle is empty (Gabriel), else the smallest incident circumradius.
+    // In a Delaunay triangulation only the opposite vertices can lie in that circle.
+    pub fn edge_alpha_values(&self) -> HashMap<Edge, f32> {
+        let mut opposite: HashMap<Edge, Vec<usize>> = HashMap::new();
+        for t in self.triangulation.chunks(3) {
+            for ((a, b), c) in triangle_edges(t).into_iter().zip([t[2], t[0], t[1]]) {
+                opposite.entry(Edge(min(a, b), max(a, b))).or_default().push(c);
+            }
+        }
+
+        opposite.into_iter()
+            .map(|(edge, others)| {
+                let (a, b) = (self.points[edge.0], self.points[edge.1]);
+                let midpoint = Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+                let half_length = a.distance(b) / 2.0;
+                let gabriel = others.iter().all(|&c| self.points[c].distance(midpoint) >= half_length);
+                let alpha = if gabriel {
+                    half_length
+                } else {
+                    others.iter()
+                        .filter_map(|&c| circumcircle_f64(a, b, self.points[c]))
+                        .map(|(_, _, r2)| r2.sqrt() as f32)
+                        .fold(f32::INFINITY, f32::min)
+                };
+                (edge, alpha)
+            })
+            .collect()
+    }
+
+    // Largest circumcircle whose center lies inside the convex hull, i.e. the biggest
+    // gap among the points. NaN center and zero radius when there is none.
+    pub fn largest_empty_circle(&self) -> (Point, f32) {
+        self.hull_circumcircles()
+            .into_iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((Point::new(f32::NAN, f32::NAN), 0.0))
+    }
+
+    // Circumcircles with at least min_radius and their center inside the hull, in triangle order
+    pub fn empty_circles(&self, min_radius: f32) -> Vec<(Point, f32)> {
+        let mut circles = self.hull_circumcircles();
+        circles.retain(|&(_, radius)| radius >= min_radius);
+        circles
+    }
+
+    fn hull_circumcircles(&self) -> Vec<(Point, f32)> {
+        let hull = self.convex_hull();
+        self.triangulation.chunks(3)
+            .filter_map(|t| circumcircle_f64(self.points[t[0]], self.points[t[1]], self.points[t[2]]))
+            .map(|(cx, cy, r2)| (Point::new(cx as f32, cy as f32), r2.sqrt() as f32))
+            .filter(|(center, _)| hull.intersects(&GeoPoint::new(center.x, center.y)))
+            .collect()
+    }
+
+    // Connectivity edges with both endpoints in the cluster, sorted
+    pub fn cluster_edges(&self, cluster: &[usize]) -> Vec<Edge> {
+        let members: HashSet<usize> = cluster.iter().copied().collect();
+        let mut edges: Vec<Edge> = members.iter()
+            .filter_map(|&v| self.geometry_data.vertex_connections.get(&v).map(|neighbors| (v, neighbors)))
+            .flat_map(|(v, neighbors)| neighbors.iter()
+                .filter(move |&&n| v < n)
+                .map(move |&n| Edge(v, n)))
+            .filter(|edge| members.contains(&edge.1))
+            .collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    // Distance to the nearest void boundary segment, negative inside a void.
+    // Infinite when there are no voids.
+    pub fn void_signed_distance(&self, p: Point, voids: &[HashSet<usize>]) -> f32 {
+        let inside = voids.iter().any(|void| self.void_contains(void, p));
+        let distance = voids.iter()
+            .flat_map(|void| self.void_boundary(void))
+            .flat_map(|ring| (0..ring.len())
+                .map(|i| (ring[i], ring[(i + 1) % ring.len()]))
+                .collect::<Vec<_>>())
+            .map(|(a, b)| point_segment_distance(p, self.points[a], self.points[b]))
+            .fold(f32::INFINITY, f32::min);
+        if inside { -distance } else { distance }
+    }
+
+    // (u, v, length) per connectivity edge with u < v, sorted
+    pub fn graph_edge_list(&self) -> Vec<(usize, usize, f32)> {
+        let mut edges: Vec<(usize, usize, f32)> = self.geometry_data.edge_lengths.iter()
+            .map(|(edge, &length)| (edge.0, edge.1, length))
+            .collect();
+        edges.sort_unstable_by_key(|&(u, v, _)| (u, v));
+        edges
+    }
+
+    // Undirected Graphviz graph, edge lengths as weight attributes written with
+    // `precision` decimal places
+    pub fn graph_to_dot(&self, precision: usize) -> String {
+        let mut dot = String::from("graph xenobalanus {\n");
+        for (u, v, length) in self.graph_edge_list() {
+            dot.push_str(&format!("  {} -- {} [weight={:.*}];\n", u, v, precision, length));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    // Chains of edge-adjacent triangle indices covering every preprocessed triangle
+    // once. Each step continues into the unvisited neighbor with the fewest
+    // unvisited neighbors of its own, which keeps the strips long.
+    pub fn triangle_strips(&self) -> Vec<Vec<usize>> {
+        let triangles = &self.geometry_data.triangles;
+        let neighbors = |index: usize| -> Vec<usize> {
+            let mut result: Vec<usize> = triangles[index].get_edges().iter()
+                .filter_map(|edge| self.geometry_data.edge_to_triangles.get(edge))
+                .flatten()
+                .copied()
+                .filter(|&n| n != index)
+                .collect();
+            result.sort_unstable();
+            result.dedup();
+            result
+        };
+
+        let mut visited = vec![false; triangles.len()];
+        let mut strips: Vec<Vec<usize>> = Vec::new();
+        for (start, _) in self.iter_triangles() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut strip = vec![start];
+            let mut current = start;
+            loop {
+                let next = neighbors(current).into_iter()
+                    .filter(|&n| !visited[n])
+                    .min_by_key(|&n| (neighbors(n).iter().filter(|&&m| !visited[m]).count(), n));
+                match next {
+                    Some(n) => {
+                        visited[n] = true;
+                        strip.push(n);
+                        current = n;
+                    }
+                    None => break,
+                }
+            }
+            strips.push(strip);
+        }
+        strips
+    }
+
+    // A tangled ring is re-ordered by angle around its vertex centroid, which gives
+    // a star-shaped, counter-clockwise simple polygon. Simple rings are returned as is.
+    pub fn repair_hull(&self, vertices: Vec<usize>) -> Vec<usize> {
+        if self.is_simple_polygon(&vertices) {
+            return vertices;
+        }
+        let mut ring: Vec<usize> = Vec::with_capacity(vertices.len());
+        let mut seen: HashSet<usize> = HashSet::new();
+        for v in vertices {
+            if seen.insert(v) {
+                ring.push(v);
+            }
+        }
+        if ring.is_empty() {
+            return ring;
+        }
+
+        let n = ring.len() as f32;
+        let (sum_x, sum_y) = ring.iter()
+            .fold((0.0, 0.0), |(x, y), &v| (x + self.points[v].x, y + self.points[v].y));
+        let center = Point::new(sum_x / n, sum_y / n);
+        let key = |v: usize| {
+            let p = self.points[v];
+            ((p.y - center.y).atan2(p.x - center.x), center.distance(p))
+        };
+        ring.sort_by(|&a, &b| {
+            let (ka, kb) = (key(a), key(b));
+            ka.0.total_cmp(&kb.0).then(ka.1.total_cmp(&kb.1)).then(a.cmp(&b))
+        });
+        ring
+    }
+
+    // Mean of 12 sqrt(3) A / P^2 over all triangles, 1 for equilateral ones and
+    // approaching 0 for slivers
+    pub fn mean_regularity(&self) -> f32 {
+        let n = self.triangulation.len() / 3;
+        if n == 0 {
+            return 0.0;
+        }
+        let total: f64 = self.triangulation.chunks(3)
+            .map(|t| {
+                let (a, b, c) = (self.points[t[0]], self.points[t[1]], self.points[t[2]]);
+                let perimeter = a.distance(b) + b.distance(c) + c.distance(a);
+                if perimeter > 0.0 {
+                    (12.0 * 3f32.sqrt() * Point::signed_area(a, b, c).abs() / perimeter.powi(2)) as f64
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+        (total / n as f64) as f32
+    }
+
+    // Square lattice from the origin, or a hexagonal one with every other row shifted
+    // by half a spacing and rows sqrt(3)/2 spacing apart (equilateral triangles)
+    pub fn lattice_points(&mut self, rows: usize, cols: usize, spacing: f32, hexagonal: bool) {
+        let row_height = if hexagonal { spacing * 3f32.sqrt() / 2.0 } else { spacing };
+        self.points.reserve(rows * cols);
+        for row in 0..rows {
+            let offset = if hexagonal && row % 2 == 1 { spacing / 2.0 } else { 0.0 };
+            for col in 0..cols {
+                self.points.push(Point::new(col as f32 * spacing + offset, row as f32 * row_height));
+            }
+        }
+    }
+
+    // Incident triangles per point divided by the triangle count, summing to 3
+    pub fn vertex_coverage(&self) -> Vec<f32> {
+        let mut counts = vec![0usize; self.points.len()];
+        for &v in &self.triangulation {
+            counts[v] += 1;
+        }
+        let total = (self.triangulation.len() / 3).max(1) as f32;
+        counts.into_iter().map(|count| count as f32 / total).collect()
+    }
+
+    // Shortest edge of a preprocessed triangle, the counterpart of its terminal edge.
+    // Equal lengths fall back to edge order like the terminal edge does.
+    pub fn min_edge(&self, index: usize) -> (Edge, f32) {
+        self.geometry_data.triangles[index].edges.iter()
+            .map(|&edge| {
+                let length = self.geometry_data.edge_lengths.get(&edge).copied()
+                    .unwrap_or_else(|| self.points[edge.0].distance_metric(self.points[edge.1], self.metric));
+                (edge, length)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)))
+            .unwrap()
+    }
+
+    // Concave hull around the pooled members of several clusters, rejected like
+    // concave_hull_area when the outline is not a simple polygon
+    pub fn clusters_union_hull(&self, clusters: &[&[usize]], alpha: f32) -> Result<Vec<usize>, XenoError> {
+        let mut vertices: Vec<usize> = clusters.iter().flat_map(|cluster| cluster.iter().copied()).collect();
+        vertices.sort_unstable();
+        vertices.dedup();
+        if let Some(&vertex) = vertices.iter().find(|&&v| v >= self.points.len()) {
+            return Err(XenoError::VertexOutOfRange { vertex, points: self.points.len() });
+        }
+        let ring = self.concave_hull(vertices, alpha);
+        if !ring.is_empty() && !self.is_simple_polygon(&ring) {
+            return Err(XenoError::SelfIntersectingHull);
+        }
+        Ok(ring)
+    }
+
+    // Area-weighted mean of the incident boundary face normals per points_3d vertex,
+    // normalized. Vertices off the boundary surface get a zero normal.
+    pub fn vertex_normals_3d(&self) -> Vec<[f32; 3]> {
+        let mut normals = vec![[0.0f32; 3]; self.points_3d.len()];
+        for face in self.boundary_faces() {
+            let a = self.points_3d[face[0]];
+            // The cross product's length is twice the face area, which supplies the weighting
+            let cross = (self.points_3d[face[1]] - a).cross(self.points_3d[face[2]] - a);
+            for &vertex in &face {
+                normals[vertex][0] += cross.x;
+                normals[vertex][1] += cross.y;
+                normals[vertex][2] += cross.z;
+            }
+        }
+        for normal in normals.iter_mut() {
+            let length = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+            if length > 0.0 {
+                normal.iter_mut().for_each(|component| *component /= length);
+            }
+        }
+        normals
+    }
+
+    // Surface of the sphere with the void's volume over the void's boundary surface,
+    // pi^(1/3) (6V)^(2/3) / A, which is 1 for a sphere and smaller otherwise
+    pub fn void_sphericity_3d(&self, void: &HashSet<usize>) -> f32 {
+        let surface: f32 = self.boundary_face_owners(|index| void.contains(&index)).iter()
+            .map(|&(face, _)| {
+                let a = self.points_3d[face[0]];
+                let cross = (self.points_3d[face[1]] - a).cross(self.points_3d[face[2]] - a);
+                cross.dot(cross).sqrt() / 2.0
+            })
+            .sum();
+        if surface > 0.0 {
+            std::f32::consts::PI.cbrt() * (6.0 * self.void_volume_3d(void)).powf(2.0 / 3.0) / surface
+        } else {
+            0.0
+        }
+    }
+
+    // Triangle pairs whose interiors overlap, for validating imported meshes.
+    // Bounding boxes are matched through an R-tree, then a separating axis test
+    // on the edge normals decides; touching along an edge or vertex is no overlap.
+    pub fn find_overlapping_triangles(&self) -> Vec<(usize, usize)> {
+        let corners = |index: usize| -> [(f64, f64); 3] {
+            let t = &self.triangulation[index * 3..index * 3 + 3];
+            [0, 1, 2].map(|k| (self.points[t[k]].x as f64, self.points[t[k]].y as f64))
+        };
+        let boxes: Vec<GeomWithData<Rectangle<[f32; 2]>, usize>> = self.triangulation.chunks(3)
+            .enumerate()
+            .filter(|(_, t)| Point::signed_area(self.points[t[0]], self.points[t[1]], self.points[t[2]]) != 0.0)
+            .map(|(index, t)| {
+                let (a, b, c) = (self.points[t[0]], self.points[t[1]], self.points[t[2]]);
+                let lower = [a.x.min(b.x).min(c.x), a.y.min(b.y).min(c.y)];
+                let upper = [a.x.max(b.x).max(c.x), a.y.max(b.y).max(c.y)];
+                GeomWithData::new(Rectangle::from_corners(lower, upper), index)
+            })
+            .collect();
+        let tree = RTree::bulk_load(boxes.clone());
+
+        let mut pairs: Vec<(usize, usize)> = Vec::new();
+        for candidate in &boxes {
+            let i = candidate.data;
+            let envelope = AABB::from_corners(candidate.geom().lower(), candidate.geom().upper());
+            for other in tree.locate_in_envelope_intersecting(&envelope) {
+                let j = other.data;
+                if j > i && !separated(&corners(i), &corners(j)) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+
+    // Points are (lon, lat) in degrees; edge lengths switch to great-circle distance
+    pub fn set_points_lonlat(&mut self, coords: Vec<(f32, f32)>) {
+        self.points = coords.into_iter().map(|(lon, lat)| Point::new(lon, lat)).collect();
+        self.metric = Metric::GreatCircle;
+        self.scalars.clear();
+        self.invalidate();
+    }
+
+    // Delaunay triangulation on the sphere, i.e. the 3D convex hull of the unit vectors.
+    // The points are projected stereographically from the first one, where the planar
+    // Delaunay triangulation of the rest equals the spherical one away from that pole
+    // and the pole joins the planar hull edges. Seamless across the antimeridian.
+    // When the points do not surround the globe's center, the hull faces turned
+    // towards it close the far side over empty space and are dropped, which leaves
+    // the regional triangulation counter-clockwise in (lon, lat).
+    pub fn delaunay_spherical(&mut self) {
+        self.halfedges.clear();
+        self.triangulation.clear();
+        let unit: Vec<[f64; 3]> = self.points.iter().map(|&p| unit_vector(p)).collect();
+        let pole = match unit.first() {
+            Some(&pole) => pole,
+            None => {
+                self.degenerate = true;
+                return;
+            }
+        };
+
+        // Orthonormal frame with the pole as its z axis
+        let helper = if pole[0].abs() < 0.9 { [1.0, 0.0, 0.0] } else { [0.0, 1.0, 0.0] };
+        let dot = |a: [f64; 3], b: [f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        let cross = |a: [f64; 3], b: [f64; 3]| [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]];
+        let e1 = cross(helper, pole);
+        let norm = dot(e1, e1).sqrt();
+        let e1 = [e1[0] / norm, e1[1] / norm, e1[2] / norm];
+        let e2 = cross(pole, e1);
+
+        // Points coinciding with the pole have no finite projection and are left out
+        let mut projected: Vec<DelaunatorPoint> = Vec::new();
+        let mut original: Vec<usize> = Vec::new();
+        for (index, &v) in unit.iter().enumerate().skip(1) {
+            let z = dot(v, pole);
+            if 1.0 - z > 1e-12 {
+                projected.push(DelaunatorPoint { x: dot(v, e1) / (1.0 - z), y: dot(v, e2) / (1.0 - z) });
+                original.push(index);
+            }
+        }
+
+        let result = triangulate(&projected);
+        self.triangulation = result.triangles.iter().map(|&v| original[v]).collect();
+        for (edge, &opposite) in result.halfedges.iter().enumerate() {
+            if opposite == delaunator::EMPTY {
+                let next = if edge % 3 == 2 { edge - 2 } else { edge + 1 };
+                let (a, b) = (original[result.triangles[edge]], original[result.triangles[next]]);
+                self.triangulation.extend([b, a, 0]);
+            }
+        }
+
+        // Faces are wound counter-clockwise from outside, so a back face's normal
+        // points at the center. Faces whose plane passes through the center join
+        // points on one great circle and are dropped as well.
+        let triangles: Vec<usize> = self.triangulation.chunks(3)
+            .filter(|t| {
+                let (a, b, c) = (unit[t[0]], unit[t[1]], unit[t[2]]);
+                let (u, v) = ([b[0] - a[0], b[1] - a[1], b[2] - a[2]], [c[0] - a[0], c[1] - a[1], c[2] - a[2]]);
+                let normal = cross(u, v);
+                dot(normal, a) > 1e-9 * dot(normal, normal).sqrt()
+            })
+            .flatten()
+            .copied()
+            .collect();
+        self.triangulation = triangles;
+        self.degenerate = self.triangulation.is_empty();
+        self.void_mask.clear();
+    }
+
+    // True when p lies in one of the void's triangles, edges included
+    fn void_contains(&self, void: &HashSet<usize>, p: Point) -> bool {
+        void.iter().any(|&i| {
+            let v = &self.geometry_data.triangles[i].vertices;
+            if v.len() < 3 {
+                return false;
+            }
+            let sides = triangle_edges(v)
+                .map(|(a, b)| orientation(self.points[a], self.points[b], p));
+            sides.iter().all(|&s| s >= 0.0) || sides.iter().all(|&s| s <= 0.0)
+        })
+    }
+
+    // Runs delfin at each threshold in increasing order. A void continues the live
+    // track whose last void shares the most triangles with it, larger tracks claiming
+    // first; voids left over start new tracks and tracks left over die at that
+    // threshold. Shared triangles rather than centroids, since the centroid of a
+    // bent void can lie outside of it.
+    pub fn void_persistence(&self, min_distances: &[f32], min_area: f32) -> Result<Vec<VoidTrack>, XenoError> {
+        let mut thresholds = min_distances.to_vec();
+        thresholds.sort_by(|a, b| a.total_cmp(b));
+
+        let mut tracks: Vec<VoidTrack> = Vec::new();
+        let mut last_voids: Vec<HashSet<usize>> = Vec::new();
+        for &threshold in &thresholds {
+            let voids = self.delfin(min_area, threshold)?;
+            let mut claimed = vec![false; voids.len()];
+
+            let mut alive: Vec<usize> = (0..tracks.len()).filter(|&t| tracks[t].death.is_none()).collect();
+            alive.sort_by(|&a, &b| tracks[b].area.total_cmp(&tracks[a].area));
+            for t in alive {
+                let matched = (0..voids.len())
+                    .filter(|&v| !claimed[v])
+                    .map(|v| (v, voids[v].intersection(&last_voids[t]).count()))
+                    .filter(|&(_, shared)| shared > 0)
+                    .max_by_key(|&(v, shared)| (shared, Reverse(v)))
+                    .map(|(v, _)| v);
+                match matched {
+                    Some(v) => {
+                        claimed[v] = true;
+                        tracks[t].centroid = self.void_centroid(&voids[v]);
+                        tracks[t].area = self.void_area(&voids[v]);
+                        last_voids[t] = voids[v].clone();
+                    }
+                    None => tracks[t].death = Some(threshold),
+                }
+            }
+
+            for (void, _) in voids.iter().zip(&claimed).filter(|&(_, &claimed)| !claimed) {
+                tracks.push(VoidTrack {
+                    birth: threshold,
+                    death: None,
+                    centroid: self.void_centroid(void),
+                    area: self.void_area(void),
+                });
+                last_voids.push(void.clone());
+            }
+        }
+        Ok(tracks)
+    }
+
+    // Smallest interior angle over 60 degrees per triangle in triangulation order,
+    // 1 for equilateral triangles and 0 for degenerate ones
+    pub fn quality_field(&self) -> Vec<f32> {
+        self.triangulation.chunks(3)
+            .map(|t| {
+                let angles = triangle_angles(self.points[t[0]], self.points[t[1]], self.points[t[2]]);
+                (angles.iter().cloned().fold(f32::MAX, f32::min) / 60.0).clamp(0.0, 1.0)
+            })
+            .collect()
+    }
+
+    // Longest chain of void triangles linked across terminal edges, the links delfin
+    // grows voids along. Every triangle links across its own terminal edge only, so
+    // the links form a tree and its longest path is found with two BFS sweeps.
+    pub fn void_spine(&self, void: &HashSet<usize>) -> Vec<usize> {
+        let mut members: Vec<usize> = void.iter().copied().collect();
+        members.sort_unstable();
+        let mut links: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &t in &members {
+            if let Some(edge) = self.geometry_data.triangles[t].terminal_edge {
+                for &n in self.geometry_data.edge_to_triangles.get(&edge).into_iter().flatten() {
+                    if n != t && void.contains(&n) {
+                        links.entry(t).or_default().push(n);
+                        links.entry(n).or_default().push(t);
+                    }
+                }
+            }
+        }
+        for neighbors in links.values_mut() {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+
+        // Breadth-first from start: the last vertex reached and everyone's predecessor
+        let sweep = |start: usize| {
+            let mut parents: HashMap<usize, Option<usize>> = HashMap::from([(start, None)]);
+            let mut queue = std::collections::VecDeque::from([start]);
+            let mut last = start;
+            while let Some(current) = queue.pop_front() {
+                last = current;
+                for &n in links.get(&current).into_iter().flatten() {
+                    if let std::collections::hash_map::Entry::Vacant(entry) = parents.entry(n) {
+                        entry.insert(Some(current));
+                        queue.push_back(n);
+                    }
+                }
+            }
+            (last, parents)
+        };
+
+        let mut seen: HashSet<usize> = HashSet::new();
+        let mut spine: Vec<usize> = Vec::new();
+        for &start in &members {
+            if seen.contains(&start) {
+                continue;
+            }
+            let (end, component) = sweep(start);
+            seen.extend(component.keys());
+            let (other_end, parents) = sweep(end);
+            let mut path = vec![other_end];
+            while let Some(&Some(parent)) = parents.get(path.last().unwrap()) {
+                path.push(parent);
+            }
+            if path.len() > spine.len() {
+                spine = path;
+            }
+        }
+        spine
+    }
+
+    // Closest (a member, b member, distance) pair between two clusters, Euclidean.
+    // b goes into an R-tree that every member of a queries for its nearest neighbor.
+    // An empty cluster gives (usize::MAX, usize::MAX, infinity).
+    pub fn cluster_gap(&self, a: &[usize], b: &[usize]) -> (usize, usize, f32) {
+        let index = RTree::bulk_load(b.iter()
+            .map(|&v| GeomWithData::new([self.points[v].x, self.points[v].y], v))
+            .collect());
+        a.iter()
+            .filter_map(|&u| {
+                let p = self.points[u];
+                index.nearest_neighbor(&[p.x, p.y]).map(|nearest| (u, nearest.data, p.distance(self.points[nearest.data])))
+            })
+            .min_by(|x, y| x.2.total_cmp(&y.2))
+            .unwrap_or((usize::MAX, usize::MAX, f32::INFINITY))
+    }
+
+    // Row-major (labels, columns, rows) grid over the bounding box, top row first as
+    // in GeoTIFF. A cell holds the 1-based index of the void containing its center,
+    // 0 for none; each void triangle only tests the cells under its bounding box.
+    pub fn rasterize_voids(&self, voids: &[HashSet<usize>], cell_size: f32) -> (Vec<u32>, usize, usize) {
+        let (lo, hi) = match self.bounding_box() {
+            Some(bounds) if cell_size > 0.0 => bounds,
+            _ => return (Vec::new(), 0, 0),
+        };
+        let columns = (((hi.x - lo.x) / cell_size).ceil() as usize).max(1);
+        let rows = (((hi.y - lo.y) / cell_size).ceil() as usize).max(1);
+        let mut labels = vec![0u32; columns * rows];
+        let center = |column: usize, row: usize| {
+            Point::new(lo.x + (column as f32 + 0.5) * cell_size, hi.y - (row as f32 + 0.5) * cell_size)
+        };
+        let cell_range = |from: f32, to: f32, cells: usize| {
+            let first = (from / cell_size - 0.5).floor().max(0.0) as usize;
+            let last = ((to / cell_size - 0.5).ceil().max(0.0) as usize).min(cells - 1);
+            first..=last
+        };
+
+        for (id, void) in voids.iter().enumerate() {
+            for &i in void {
+                let v = &self.geometry_data.triangles[i].vertices;
+                if v.len() < 3 {
+                    continue;
+                }
+                let (a, b, c) = (self.points[v[0]], self.points[v[1]], self.points[v[2]]);
+                let (min_x, max_x) = (a.x.min(b.x).min(c.x), a.x.max(b.x).max(c.x));
+                let (min_y, max_y) = (a.y.min(b.y).min(c.y), a.y.max(b.y).max(c.y));
+                for row in cell_range(hi.y - max_y, hi.y - min_y, rows) {
+                    for column in cell_range(min_x - lo.x, max_x - lo.x, columns) {
+                        let p = center(column, row);
+                        let sides = [orientation(a, b, p), orientation(b, c, p), orientation(c, a, p)];
+                        if sides.iter().all(|&s| s >= 0.0) || sides.iter().all(|&s| s <= 0.0) {
+                            labels[row * columns + column] = id as u32 + 1;
+                        }
+                    }
+                }
+            }
+        }
+        (labels, columns, rows)
+    }
+}
+
+// Fluent setup: triangulates and preprocesses in build(), which rejects empty point sets
+#[derive(Debug, Default)]
+pub struct XenobalanusBuilder {
+    points: Vec<Point>,
+    preprocess_mode: PreprocessMode,
+    metric: Metric,
+    parallel: bool,
+}
+
+impl XenobalanusBuilder {
+    pub fn points(mut self, points: Vec<Point>) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn preprocess_mode(mut self, preprocess_mode: PreprocessMode) -> Self {
+        self.preprocess_mode = preprocess_mode;
+        self
+    }
+
+    pub fn metric(mut self, metric: Metric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    pub fn build(self) -> Result<Xenobalanus, XenoError> {
+        if self.points.is_empty() {
+            return Err(XenoError::NoPoints);
+        }
+        let mut xeno = Xenobalanus::with_capacity(self.points.len());
+        xeno.add_points(self.points);
+        xeno.set_metric(self.metric);
+        xeno.delaunay();
+        xeno.preprocess(self.preprocess_mode.types(), self.parallel);
+        Ok(xeno)
+    }
+}
+
+// Unit vector of a (lon, lat) point in degrees
+fn unit_vector(p: Point) -> [f64; 3] {
+    let (lon, lat) = ((p.x as f64).to_radians(), (p.y as f64).to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+// Square degrees on the sphere for great-circle data, planar area otherwise
+fn triangle_area(a: Point, b: Point, c: Point, metric: Metric) -> f32 {
+    match metric {
+        Metric::GreatCircle => Point::spherical_area(a, b, c),
+        _ => Point::signed_area(a, b, c).abs(),
+    }
+}
+
+// The three directed edges of a triangle's vertex triple
+fn triangle_edges(t: &[usize]) -> [(usize, usize); 3] {
+    [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])]
+}
+
+// Positive when a-b-c turns counter-clockwise, zero when collinear
+fn orientation(a: Point, b: Point, c: Point) -> f32 {
+    Point::signed_area(a, b, c)
+}
+
+fn on_segment(a: Point, b: Point, p: Point) -> bool {
+    p.x >= a.x.min(b.x) && p.x <= a.x.max(b.x) && p.y >= a.y.min(b.y) && p.y <= a.y.max(b.y)
+}
+
+// True if segment a1-a2 touches or crosses segment b1-b2
+fn segments_intersect(a1: Point, a2: Point, b1: Point, b2: Point) -> bool {
+    let d1 = orientation(b1, b2, a1);
+    let d2 = orientation(b1, b2, a2);
+    let d3 = orientation(a1, a2, b1);
+    let d4 = orientation(a1, a2, b2);
+
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0)) && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0)) {
+        return true;
+    }
+    (d1 == 0.0 && on_segment(b1, b2, a1))
+        || (d2 == 0.0 && on_segment(b1, b2, a2))
+        || (d3 == 0.0 && on_segment(a1, a2, b1))
+        || (d4 == 0.0 && on_segment(a1, a2, b2))
+}
+
+// Sorted faces that belong to exactly one of the tetrahedra, each with the position
+// of that tetrahedron. Tetrahedra repeating a vertex are skipped.
+fn unshared_faces(tetrahedra: &[[usize; 4]]) -> Vec<([usize; 3], usize)> {
+    let mut faces: HashMap<[usize; 3], (usize, usize)> = HashMap::new();
+    for (index, t) in tetrahedra.iter().enumerate() {
+        if (0..4).any(|i| t[i + 1..].contains(&t[i])) {
+            continue;
+        }
+        for mut face in [[t[1], t[2], t[3]], [t[0], t[2], t[3]], [t[0], t[1], t[3]], [t[0], t[1], t[2]]] {
+            face.sort_unstable();
+            faces.entry(face).or_insert((0, index)).0 += 1;
+        }
+    }
+    let mut boundary: Vec<([usize; 3], usize)> = faces.into_iter()
+        .filter(|&(_, (count, _))| count == 1)
+        .map(|(face, (_, owner))| (face, owner))
+        .collect();
+    boundary.sort_unstable();
+    boundary
+}
+
+// Winds a face of the tetrahedron so its normal points away from the fourth vertex
+fn outward_face(points: &[Point3], face: [usize; 3], tetrahedron: &[usize]) -> [usize; 3] {
+    let apex = tetrahedron.iter().copied().find(|v| !face.contains(v)).unwrap();
+    let a = points[face[0]];
+    let normal = (points[face[1]] - a).cross(points[face[2]] - a);
+    if normal.dot(points[apex] - a) > 0.0 {
+        [face[0], face[2], face[1]]
+    } else {
+        face
+    }
+}
+
+// Circumcenter and squared circumradius, None for flat tetrahedra
+fn circumsphere_f64(a: Point3, b: Point3, c: Point3, d: Point3) -> Option<([f64; 3], f64)> {
+    let origin = [a.x as f64, a.y as f64, a.z as f64];
+    let offset = |p: Point3| [p.x as f64 - origin[0], p.y as f64 - origin[1], p.z as f64 - origin[2]];
+    let cross = |u: [f64; 3], v: [f64; 3]| [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+    let dot = |u: [f64; 3], v: [f64; 3]| u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+    let (u, v, w) = (offset(b), offset(c), offset(d));
+    let (vw, wu, uv) = (cross(v, w), cross(w, u), cross(u, v));
+    let denominator = 2.0 * dot(u, vw);
+    if denominator == 0.0 {
+        return None;
+    }
+    let (lu, lv, lw) = (dot(u, u), dot(v, v), dot(w, w));
+    let center = [
+        (lu * vw[0] + lv * wu[0] + lw * uv[0]) / denominator,
+        (lu * vw[1] + lv * wu[1] + lw * uv[1]) / denominator,
+        (lu * vw[2] + lv * wu[2] + lw * uv[2]) / denominator,
+    ];
+    Some(([origin[0] + center[0], origin[1] + center[1], origin[2] + center[2]], dot(center, center)))
+}
+
+// Stands in for the point at infinity in the ghost cells of a Tetrahedralization
+const INFINITE_VERTEX: usize = usize::MAX;
+
+// Delaunay tetrahedralization as positively oriented index quadruples into points.
+// Duplicates are skipped, input without four non-coplanar points gives none.
+fn tetrahedralize(points: &[Point3]) -> Vec<[usize; 4]> {
+    // Inserting along a Morton curve keeps the point location walks short
+    let Some(&first) = points.first() else { return Vec::new() };
+    let (lo, hi) = points.iter().fold((first, first), |(lo, hi), p| {
+        (Point3::new(lo.x.min(p.x), lo.y.min(p.y), lo.z.min(p.z)), Point3::new(hi.x.max(p.x), hi.y.max(p.y), hi.z.max(p.z)))
+    });
+    let size = (hi.x - lo.x).max(hi.y - lo.y).max(hi.z - lo.z).max(f32::EPSILON);
+    let morton = |p: Point3| -> u32 {
+        [p.x - lo.x, p.y - lo.y, p.z - lo.z].iter().enumerate().fold(0, |code, (axis, &offset)| {
+            let cell = ((offset / size) * 1023.0) as u32;
+            (0..10).fold(code, |code, bit| code | ((cell >> bit) & 1) << (3 * bit + axis))
+        })
+    };
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by_key(|&v| morton(points[v]));
+
+    let Some(mut mesh) = Tetrahedralization::new(points, &order) else { return Vec::new() };
+    for &v in &order {
+        mesh.insert(v);
+    }
+    mesh.cells.iter()
+        .zip(&mesh.dead)
+        .filter(|&(t, &dead)| !dead && !t.contains(&INFINITE_VERTEX))
+        .map(|(&t, _)| t)
+        .collect()
+}
+
+// Bowyer-Watson insertion with exact orientation and insphere predicates. The hull
+// faces carry ghost cells on INFINITE_VERTEX, so points outside the hull are
+// inserted like any other and the hull is recovered exactly. Cells are oriented so
+// that orient3d is positive, a ghost cell as if its infinite vertex lay beyond its
+// hull face. adjacent[c][i] is the cell across the face opposite cells[c][i].
+struct Tetrahedralization<'a> {
+    points: &'a [Point3],
+    cells: Vec<[usize; 4]>,
+    adjacent: Vec<[usize; 4]>,
+    dead: Vec<bool>,
+    free: Vec<usize>,
+    // Finite cell the next point location walk starts from
+    last: usize,
+}
+
+impl<'a> Tetrahedralization<'a> {
+    // Seeds the mesh with the first non-degenerate tetrahedron found in order and
+    // its four ghost cells
+    fn new(points: &'a [Point3], order: &[usize]) -> Option<Self> {
+        let mut mesh = Tetrahedralization { points, cells: Vec::new(), adjacent: Vec::new(), dead: Vec::new(), free: Vec::new(), last: 0 };
+        let a = order[0];
+        let b = *order.iter().find(|&&v| mesh.coordinates(v) != mesh.coordinates(a))?;
+        let c = *order.iter().find(|&&v| !mesh.collinear(a, b, v))?;
+        let d = *order.iter().find(|&&v| mesh.orient([a, b, c, v]) != 0.0)?;
+        let seed = if mesh.orient([a, b, c, d]) > 0.0 { [a, b, c, d] } else { [b, a, c, d] };
+
+        mesh.cells.push(seed);
+        for i in 0..4 {
+            // The infinite vertex lies across the face from seed[i], which flips the orientation
+            let mut ghost = seed;
+            ghost[i] = INFINITE_VERTEX;
+            ghost.swap((i + 1) % 4, (i + 2) % 4);
+            mesh.cells.push(ghost);
+        }
+        mesh.adjacent = vec![[usize::MAX; 4]; 5];
+        mesh.dead = vec![false; 5];
+        let mut faces: HashMap<[usize; 3], (usize, usize)> = HashMap::new();
+        for cell in 0..5 {
+            for i in 0..4 {
+                let mut face = mesh.face(cell, i);
+                face.sort_unstable();
+                if let Some((other, j)) = faces.remove(&face) {
+                    mesh.adjacent[cell][i] = other;
+                    mesh.adjacent[other][j] = cell;
+                } else {
+                    faces.insert(face, (cell, i));
+                }
+            }
+        }
+        Some(mesh)
+    }
+
+    fn coordinates(&self, v: usize) -> robust::Coord3D<f64> {
+        let p = self.points[v];
+        robust::Coord3D { x: p.x as f64, y: p.y as f64, z: p.z as f64 }
+    }
+
+    fn orient(&self, t: [usize; 4]) -> f64 {
+        robust::orient3d(self.coordinates(t[0]), self.coordinates(t[1]), self.coordinates(t[2]), self.coordinates(t[3]))
+    }
+
+    // Three points are collinear when their projections on all coordinate planes are
+    fn collinear(&self, a: usize, b: usize, c: usize) -> bool {
+        let [a, b, c] = [a, b, c].map(|v| self.coordinates(v));
+        let planes: [fn(robust::Coord3D<f64>) -> robust::Coord<f64>; 3] = [
+            |p| robust::Coord { x: p.x, y: p.y },
+            |p| robust::Coord { x: p.y, y: p.z },
+            |p| robust::Coord { x: p.z, y: p.x },
+        ];
+        planes.iter().all(|plane| robust::orient2d(plane(a), plane(b), plane(c)) == 0.0)
+    }
+
+    fn face(&self, cell: usize, i: usize) -> [usize; 3] {
+        let t = self.cells[cell];
+        [t[(i + 1) % 4], t[(i + 2) % 4], t[(i + 3) % 4]]
+    }
+
+    // Whether p lies inside the open circumsphere of the cell. For a ghost cell that is
+    // the half-space beyond its hull face, and on the face's plane the circumcircle of
+    // the face, which the circumsphere of the finite cell behind it cuts out.
+    fn in_conflict(&self, cell: usize, p: usize) -> bool {
+        let t = self.cells[cell];
+        match t.iter().position(|&v| v == INFINITE_VERTEX) {
+            None => {
+                let [a, b, c, d] = t.map(|v| self.coordinates(v));
+                robust::insphere(a, b, c, d, self.coordinates(p)) > 0.0
+            }
+            Some(k) => {
+                let mut beyond = t;
+                beyond[k] = p;
+                let side = self.orient(beyond);
+                side > 0.0 || (side == 0.0 && self.in_conflict(self.adjacent[cell][k], p))
+            }
+        }
+    }
+
+    // A cell in conflict with p, found by walking towards p from the last finite cell.
+    // None when p duplicates a vertex.
+    fn locate(&self, p: usize) -> Option<usize> {
+        let mut cell = self.last;
+        // Rotating the first face tested breaks the cycles degenerate input can cause
+        for step in 0..self.cells.len() {
+            if self.cells[cell].contains(&INFINITE_VERTEX) {
+                return Some(cell);
+            }
+            let across = (0..4).map(|k| (k + step) % 4).find(|&i| {
+                let mut moved = self.cells[cell];
+                moved[i] = p;
+                self.orient(moved) < 0.0
+            });
+            match across {
+                Some(i) => cell = self.adjacent[cell][i],
+                None => return Some(cell).filter(|&cell| self.in_conflict(cell, p)),
+            }
+        }
+        (0..self.cells.len()).find(|&cell| !self.dead[cell] && self.in_conflict(cell, p))
+    }
+
+    fn insert(&mut self, p: usize) {
+        let Some(start) = self.locate(p) else { return };
+
+        // The cavity is star-shaped around p, so flooding outward from start finds all of it
+        let mut cavity = vec![start];
+        let mut in_cavity: HashSet<usize> = HashSet::from([start]);
+        let mut outside: HashSet<usize> = HashSet::new();
+        let mut boundary: Vec<(usize, usize)> = Vec::new();
+        let mut next = 0;
+        while let Some(&cell) = cavity.get(next) {
+            next += 1;
+            for i in 0..4 {
+                let neighbor = self.adjacent[cell][i];
+                if in_cavity.contains(&neighbor) {
+                    continue;
+                }
+                if !outside.contains(&neighbor) && self.in_conflict(neighbor, p) {
+                    in_cavity.insert(neighbor);
+                    cavity.push(neighbor);
+                } else {
+                    outside.insert(neighbor);
+                    boundary.push((cell, i));
+                }
+            }
+        }
+
+        // Cone the cavity boundary to p; new cells sharing an edge of the boundary
+        // are neighbors across the face spanned by that edge and p
+        let mut edges: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        for (cell, i) in boundary {
+            let mut t = self.cells[cell];
+            t[i] = p;
+            let outer = self.adjacent[cell][i];
+            let created = self.allocate(t);
+            self.adjacent[created][i] = outer;
+            let back = (0..4).find(|&j| self.adjacent[outer][j] == cell).unwrap();
+            self.adjacent[outer][back] = created;
+            for j in (0..4).filter(|&j| j != i) {
+                let mut rest = (0..4).filter(|&k| k != i && k != j).map(|k| t[k]);
+                let (u, w) = (rest.next().unwrap(), rest.next().unwrap());
+                match edges.remove(&(min(u, w), max(u, w))) {
+                    Some((other, k)) => {
+                        self.adjacent[created][j] = other;
+                        self.adjacent[other][k] = created;
+                    }
+                    None => {
+                        edges.insert((min(u, w), max(u, w)), (created, j));
+                    }
+                }
+            }
+            if !t.contains(&INFINITE_VERTEX) {
+                self.last = created;
+            }
+        }
+        for cell in cavity {
+            self.dead[cell] = true;
+            self.free.push(cell);
+        }
+    }
+
+    fn allocate(&mut self, t: [usize; 4]) -> usize {
+        match self.free.pop() {
+            Some(cell) => {
+                self.cells[cell] = t;
+                self.dead[cell] = false;
+                cell
+            }
+            None => {
+                self.cells.push(t);
+                self.adjacent.push([usize::MAX; 4]);
+                self.dead.push(false);
+                self.cells.len() - 1
+            }
+        }
+    }
+}
+
+// Circumcenter and squared circumradius, None for degenerate triangles
+fn circumcircle_f64(a: Point, b: Point, c: Point) -> Option<(f64, f64, f64)> {
+    let (ax, ay) = (a.x as f64, a.y as f64);
+    let (bx, by) = (b.x as f64 - ax, b.y as f64 - ay);
+    let (cx, cy) = (c.x as f64 - ax, c.y as f64 - ay);
+    let d = 2.0 * (bx * cy - by * cx);
+    if d == 0.0 {
+        return None;
+    }
+    let b2 = bx * bx + by * by;
+    let c2 = cx * cx + cy * cy;
+    let ux = (cy * b2 - by * c2) / d;
+    let uy = (bx * c2 - cx * b2) / d;
+    Some((ax + ux, ay + uy, ux * ux + uy * uy))
+}
+
+// Interior angles in degrees at a, b and c
+fn triangle_angles(a: Point, b: Point, c: Point) -> [f32; 3] {
+    let angle = |at: Point, p: Point, q: Point| {
+        let (ux, uy) = (p.x - at.x, p.y - at.y);
+        let (vx, vy) = (q.x - at.x, q.y - at.y);
+        (ux * vy - uy * vx).abs().atan2(ux * vx + uy * vy).to_degrees()
+    };
+    [angle(a, b, c), angle(b, c, a), angle(c, a, b)]
+}
+
+// Joins undirected boundary edges into closed vertex rings
+fn chain_rings(mut boundary: Vec<Edge>) -> Vec<Vec<usize>> {
+    boundary.sort_unstable_by_key(|e| (e.0, e.1));
+
+    let mut adjacency: HashMap<usize, Vec<usize>> = HashMap::new();
+    for edge in &boundary {
+        adjacency.entry(edge.0).or_default().push(edge.1);
+        adjacency.entry(edge.1).or_default().push(edge.0);
+    }
+
+    let mut used: HashSet<Edge> = HashSet::new();
+    let mut rings: Vec<Vec<usize>> = Vec::new();
+    for edge in boundary {
+        if used.contains(&edge) {
+            continue;
+        }
+        used.insert(edge);
+        let mut ring = vec![edge.0];
+        let mut current = edge.1;
+        while current != edge.0 {
+            ring.push(current);
+            let next = adjacency[&current].iter()
+                .copied()
+                .find(|&n| !used.contains(&Edge(min(current, n), max(current, n))));
+            match next {
+                Some(n) => {
+                    used.insert(Edge(min(current, n), max(current, n)));
+                    current = n;
+                }
+                None => break,
+            }
+        }
+        rings.push(ring);
+    }
+    rings
+}
+
+// Chains directed boundary edges, with the region on their left, into closed
+// rings. At a vertex with several outgoing edges the walk takes the first one
+// clockwise from where it came from, which stays in the wedge of the region it
+// arrived through instead of crossing into a ring touching it there.
+fn trace_rings(points: &[Point], mut boundary: Vec<(usize, usize)>) -> Vec<Vec<usize>> {
+    boundary.sort_unstable();
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(from, to) in &boundary {
+        outgoing.entry(from).or_default().push(to);
+    }
+    let bearing = |from: usize, to: usize| {
+        let (p, q) = (points[from], points[to]);
+        ((q.y - p.y) as f64).atan2((q.x - p.x) as f64)
+    };
+    // Clockwise turn from the edge back to prev round to the edge towards next, in (0, 2pi]
+    let clockwise = |prev: usize, current: usize, next: usize| {
+        let turn = (bearing(current, prev) - bearing(current, next)).rem_euclid(std::f64::consts::TAU);
+        if turn == 0.0 { std::f64::consts::TAU } else { turn }
+    };
+
+    let mut used: HashSet<(usize, usize)> = HashSet::new();
+    let mut rings: Vec<Vec<usize>> = Vec::new();
+    for start in boundary {
+        if !used.insert(start) {
+            continue;
+        }
+        let mut ring = vec![start.0];
+        let (mut prev, mut current) = start;
+        loop {
+            let next = outgoing.get(&current).and_then(|targets| targets.iter()
+                .copied()
+                .min_by(|&a, &b| clockwise(prev, current, a).total_cmp(&clockwise(prev, current, b))));
+            let Some(next) = next else {
+                ring.push(current);
+                break;
+            };
+            if (current, next) == start || !used.insert((current, next)) {
+                break;
+            }
+            ring.push(current);
+            (prev, current) = (current, next);
+        }
+        rings.push(ring);
+    }
+    rings
+}
+
+// Total ordering for non-NaN distances in priority queues
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OrderedDistance(f32);
+
+impl Eq for OrderedDistance {}
+
+impl PartialOrd for OrderedDistance {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedDistance {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// Swiss-table layout: a power-of-two bucket count at 7/8 load, one control byte per bucket
+fn hash_table_bytes<T>(capacity: usize) -> usize {
+    if capacity == 0 {
+        return 0;
+    }
+    let buckets = (capacity * 8 / 7).next_power_of_two();
+    buckets * (std::mem::size_of::<T>() + 1)
+}
+
+// Sutherland-Hodgman step keeping the side of the line through `origin` that `normal` points away from
+fn clip_half_plane(polygon: &[(f64, f64)], origin: (f64, f64), normal: (f64, f64)) -> Vec<(f64, f64)> {
+    let side = |p: (f64, f64)| (p.0 - origin.0) * normal.0 + (p.1 - origin.1) * normal.1;
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &current) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        let (s_current, s_next) = (side(current), side(next));
+        if s_current <= 0.0 {
+            clipped.push(current);
+        }
+        if (s_current < 0.0 && s_next > 0.0) || (s_current > 0.0 && s_next < 0.0) {
+            let t = s_current / (s_current - s_next);
+            clipped.push((current.0 + t * (next.0 - current.0), current.1 + t * (next.1 - current.1)));
+        }
+    }
+    clipped
+}
+
+fn point_segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length2 = dx * dx + dy * dy;
+    let t = if length2 > 0.0 {
+        (((p.x - a.x) * dx + (p.y - a.y) * dy) / length2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    p.distance(Point::new(a.x + t * dx, a.y + t * dy))
+}
+
+// Separating axis test for two triangles, projections that only touch count as separated
+fn separated(a: &[(f64, f64); 3], b: &[(f64, f64); 3]) -> bool {
+    [a, b].iter().any(|triangle| {
+        (0..3).any(|k| {
+            let (p, q) = (triangle[k], triangle[(k + 1) % 3]);
+            let axis = (p.1 - q.1, q.0 - p.0);
+            let project = |corners: &[(f64, f64); 3]| {
+                corners.iter()
+                    // Relative to p, so the shared edge of neighbors projects to exactly 0
+                    .map(|c| (c.0 - p.0) * axis.0 + (c.1 - p.1) * axis.1)
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), d| (lo.min(d), hi.max(d)))
+            };
+            let ((a_lo, a_hi), (b_lo, b_hi)) = (project(a), project(b));
+            a_hi <= b_lo || b_hi <= a_lo
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::io::Cursor;
+
+    // Counts allocations per thread, so tests running alongside don't interfere
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocations_during(f: impl FnOnce()) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    // Unit grid over [0, n) x [0, n) jittered by up to 0.2, without the points
+    // closer than radius to center
+    fn grid_with_hole(n: usize, center: Point, radius: f32, seed: u64) -> Vec<Point> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut points = Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                let p = Point::new(i as f32 + rng.gen_range(-0.2..0.2), j as f32 + rng.gen_range(-0.2..0.2));
+                if p.distance(center) >= radius {
+                    points.push(p);
+                }
+            }
+        }
+        points
+    }
+
+    // Triangulated and fully preprocessed
+    fn analyzed(points: Vec<Point>) -> Xenobalanus {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(points);
+        xeno.delaunay();
+        xeno.preprocess(0, false);
+        xeno
+    }
+
+    // Square [0, 3]^2 around the hole [1, 2]^2, eight triangles
+    fn annulus() -> Xenobalanus {
+        let points = [(0.0, 0.0), (3.0, 0.0), (3.0, 3.0), (0.0, 3.0), (1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)]
+            .map(|(x, y)| Point::new(x, y))
+            .to_vec();
+        let triangles = vec![0, 1, 5, 0, 5, 4, 1, 2, 6, 1, 6, 5, 2, 3, 7, 2, 7, 6, 3, 0, 4, 3, 4, 7];
+        let mut xeno = Xenobalanus::new();
+        xeno.set_mesh(points, triangles).unwrap();
+        xeno.preprocess(0, false);
+        xeno
+    }
+
+    // Unit cube split into six tetrahedra around the 0-6 diagonal
+    fn cube() -> Xenobalanus {
+        let corners = [(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 1.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0), (1.0, 0.0, 1.0), (1.0, 1.0, 1.0), (0.0, 1.0, 1.0)];
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points_3d(corners.iter().map(|&(x, y, z)| Point3::new(x, y, z)).collect());
+        xeno.set_tetrahedra(vec![0, 1, 2, 6, 0, 2, 3, 6, 0, 3, 7, 6, 0, 7, 4, 6, 0, 4, 5, 6, 0, 5, 1, 6]);
+        xeno
+    }
+
+    fn centroid_of(xeno: &Xenobalanus, vertices: &[usize]) -> Point {
+        let n = vertices.len() as f32;
+        let (x, y) = vertices.iter().fold((0.0, 0.0), |(x, y), &v| (x + xeno.point(v).x, y + xeno.point(v).y));
+        Point::new(x / n, y / n)
+    }
+
+    #[test]
+    fn triangulated_area_of_the_unit_square() {
+        let xeno = analyzed(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(1.0, 1.0), Point::new(0.0, 1.0)]);
+        assert!((xeno.triangulated_area().unwrap() - 1.0).abs() < 1e-6);
+        assert!(matches!(Xenobalanus::new().triangulated_area(), Err(XenoError::AreasNotComputed)));
+    }
+
+    #[test]
+    fn cached_edges_match_the_vertices() {
+        let xeno = analyzed(grid_with_hole(8, Point::new(-10.0, -10.0), 0.0, 1));
+        for (_, triangle) in xeno.iter_triangles() {
+            let v = &triangle.vertices;
+            let mut expected = vec![Edge(v[0], v[1]), Edge(v[1], v[2]), Edge(v[0], v[2])];
+            let mut cached = triangle.get_edges().to_vec();
+            expected.sort_unstable();
+            cached.sort_unstable();
+            assert_eq!(cached, expected);
+        }
+    }
+
+    #[test]
+    fn delfin_does_not_allocate_per_triangle() {
+        let xeno = analyzed(grid_with_hole(100, Point::new(50.0, 50.0), 40.0, 1));
+        let triangles: Vec<&TriangleData> = xeno.iter_triangles().map(|(_, t)| t).collect();
+        assert_eq!(allocations_during(|| triangles.iter().for_each(|t| assert_eq!(t.get_edges().len(), 3))), 0);
+
+        // Fetching the edges of every void member once allocated at least once per
+        // member, now only the growing sets allocate
+        let mut voids = Vec::new();
+        let allocations = allocations_during(|| voids = xeno.delfin(0.0, 2.0).unwrap());
+        let members: usize = voids.iter().map(|void| void.len()).sum();
+        assert!(members > 500);
+        assert!(allocations < members / 2, "{} allocations for {} void triangles", allocations, members);
+    }
+
+    #[test]
+    fn largest_void_is_the_planted_hole() {
+        let xeno = analyzed(grid_with_hole(20, Point::new(12.0, 8.0), 4.0, 2));
+        let void = xeno.largest_void(2.5).unwrap().unwrap();
+        assert!(xeno.void_centroid(&void).distance(Point::new(12.0, 8.0)) < 1.0);
+    }
+
+    #[test]
+    fn metrics_on_a_three_four_offset() {
+        let (a, b) = (Point::new(1.0, 1.0), Point::new(4.0, 5.0));
+        assert_eq!(a.distance_metric(b, Metric::Euclidean), 5.0);
+        assert_eq!(a.distance_metric(b, Metric::Manhattan), 7.0);
+        assert_eq!(a.distance_metric(b, Metric::Chebyshev), 4.0);
+    }
+
+    #[test]
+    fn vertex_edges_match_vertex_connections() {
+        let mut xeno = analyzed(grid_with_hole(6, Point::new(-10.0, -10.0), 0.0, 3));
+        xeno.build_vertex_edge_index();
+        for (&vertex, neighbors) in xeno.vertex_connections() {
+            let mut expected: Vec<Edge> = neighbors.iter().map(|&n| Edge(min(vertex, n), max(vertex, n))).collect();
+            expected.sort_unstable();
+            assert_eq!(xeno.vertex_edges(vertex), expected.as_slice());
+        }
+    }
+
+    #[test]
+    fn dtscan_in_region_stays_inside_the_region() {
+        let xeno = analyzed(grid_with_hole(12, Point::new(-10.0, -10.0), 0.0, 4));
+        let region = Polygon::new(LineString::from(vec![(2.5, 2.5), (8.5, 2.5), (8.5, 8.5), (2.5, 8.5), (2.5, 2.5)]), Vec::new());
+        let inside = |v: &usize| region.contains(&GeoPoint::new(xeno.point(*v).x, xeno.point(*v).y));
+
+        let full: Vec<usize> = xeno.dtscan(3, 1.6).into_iter().flatten().collect();
+        let restricted: Vec<usize> = xeno.dtscan_in_region(region.clone(), 3, 1.6).into_iter().flatten().collect();
+        assert!(!restricted.is_empty());
+        assert!(restricted.iter().all(inside));
+        assert!(restricted.len() < full.len());
+        assert!(restricted.len() <= full.iter().filter(|v| inside(v)).count());
+    }
+
+    #[test]
+    fn distance_matrix_cells() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(vec![Point::new(0.0, 0.0), Point::new(3.0, 4.0), Point::new(6.0, 8.0)]);
+        let matrix = xeno.distance_matrix(&[0, 1], &[1, 2]);
+        assert_eq!(matrix, vec![vec![5.0, 10.0], vec![0.0, 5.0]]);
+    }
+
+    #[test]
+    fn subsample_hits_the_target_and_keeps_the_bounding_box() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(grid_with_hole(30, Point::new(-10.0, -10.0), 0.0, 5));
+        xeno.set_scalars((0..900).map(|i| i as f32).collect());
+        let (lo, hi) = xeno.bounding_box().unwrap();
+
+        let kept = xeno.subsample(300, 1);
+        assert_eq!(kept.len(), xeno.points().len());
+        assert!((250..=350).contains(&kept.len()), "kept {}", kept.len());
+        let (new_lo, new_hi) = xeno.bounding_box().unwrap();
+        assert_eq!((new_lo.x, new_lo.y, new_hi.x, new_hi.y), (lo.x, lo.y, hi.x, hi.y));
+        // Scalars follow their points
+        assert_eq!(xeno.scalars(), kept.iter().map(|&i| i as f32).collect::<Vec<f32>>().as_slice());
+    }
+
+    #[test]
+    fn annular_void_has_one_hole() {
+        let xeno = annulus();
+        let void: HashSet<usize> = (0..8).collect();
+        let polygon = xeno.void_polygon_with_holes(&void);
+        assert_eq!(polygon.interiors().len(), 1);
+        assert!((polygon.unsigned_area() - 8.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn figure_eight_void_splits_at_the_pinch() {
+        // Two unit squares touching at the corner (1, 1), vertex 6
+        let points = [(2.0, 2.0), (2.0, 1.0), (1.0, 0.0), (0.0, 0.0), (0.0, 1.0), (1.0, 2.0), (1.0, 1.0)]
+            .map(|(x, y)| Point::new(x, y))
+            .to_vec();
+        let mut xeno = Xenobalanus::new();
+        xeno.set_mesh(points, vec![0, 1, 6, 0, 6, 5, 6, 2, 3, 6, 3, 4]).unwrap();
+        xeno.preprocess(0, false);
+
+        let mut rings = xeno.void_boundary(&(0..4).collect());
+        for ring in rings.iter_mut() {
+            let first = ring.iter().position(|&v| v == 6).unwrap();
+            ring.rotate_left(first);
+        }
+        rings.sort();
+        assert_eq!(rings, vec![vec![6, 1, 0, 5], vec![6, 4, 3, 2]]);
+    }
+
+    #[test]
+    fn cluster_labels_point_back_to_their_cluster() {
+        let mut points = grid_with_hole(6, Point::new(-10.0, -10.0), 0.0, 6);
+        points.extend(grid_with_hole(6, Point::new(-10.0, -10.0), 0.0, 7).iter().map(|p| Point::new(p.x + 20.0, p.y)));
+        let xeno = analyzed(points);
+        let clusters = xeno.dtscan(3, 1.6);
+        assert!(clusters.len() >= 2);
+        let labels = xeno.cluster_labels(&clusters);
+        for (vertex, label) in labels.iter().enumerate() {
+            if let Some(id) = label {
+                assert!(clusters[*id].contains(&vertex));
+            }
+        }
+        assert_eq!(labels.iter().flatten().count(), clusters.iter().map(|c| c.len()).sum::<usize>());
+    }
+
+    #[test]
+    fn higher_area_percentile_gives_fewer_voids() {
+        let xeno = analyzed(grid_with_hole(20, Point::new(-10.0, -10.0), 0.0, 8));
+        let low = xeno.delfin_percentile(0.0, 1.0).unwrap().len();
+        let high = xeno.delfin_percentile(99.0, 1.0).unwrap().len();
+        assert!(high < low, "{} voids at the 99th percentile, {} at the 0th", high, low);
+    }
+
+    #[test]
+    fn hand_built_mesh_runs_through_delfin() {
+        // Square with its center, four triangles whose terminal edge is the outline
+        let points = vec![Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 2.0), Point::new(0.0, 2.0), Point::new(1.0, 1.0)];
+        let mut xeno = Xenobalanus::new();
+        xeno.set_mesh(points.clone(), vec![0, 1, 4, 1, 2, 4, 2, 3, 4, 3, 0, 4]).unwrap();
+        xeno.preprocess(0, false);
+        xeno.set_min_triangles(1);
+        let voids = xeno.delfin(0.0, 0.0).unwrap();
+        assert_eq!(voids.len(), 4);
+        assert!(voids.iter().all(|void| void.len() == 1));
+
+        assert!(matches!(xeno.set_mesh(points.clone(), vec![0, 1]), Err(XenoError::InvalidMeshLength(2))));
+        assert!(matches!(xeno.set_mesh(points, vec![0, 1, 5]), Err(XenoError::VertexOutOfRange { vertex: 5, points: 5 })));
+    }
+
+    #[test]
+    fn simple_and_figure_eight_rings() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(1.0, 1.0), Point::new(0.0, 1.0)]);
+        assert!(xeno.is_simple_polygon(&[0, 1, 2, 3]));
+        assert!(xeno.is_simple_polygon(&[0, 1, 2, 3, 0]));
+        assert!(!xeno.is_simple_polygon(&[0, 2, 1, 3]));
+    }
+
+    #[test]
+    fn density_weighting_drops_sparse_region_voids() {
+        // Spacing grows from 0.5 on the left to 2 on the right
+        let mut rng = StdRng::seed_from_u64(9);
+        let density = |p: Point| 4.0 / (1.0 + p.x / 10.0).powi(2);
+        let mut points = Vec::new();
+        while points.len() < 1500 {
+            let p = Point::new(rng.gen_range(0.0..30.0), rng.gen_range(0.0..30.0));
+            if rng.gen::<f32>() * 4.0 < density(p) {
+                points.push(p);
+            }
+        }
+        let xeno = analyzed(points);
+        let plain = xeno.delfin(0.0, 1.0).unwrap();
+        let weighted = xeno.delfin_adaptive(density, 4.0, 1.0).unwrap();
+        let sparse = |voids: &[HashSet<usize>]| voids.iter().filter(|v| xeno.void_centroid(v).x > 20.0).count();
+        assert!(weighted.len() < plain.len());
+        assert!(sparse(&weighted) < sparse(&plain));
+    }
+
+    #[test]
+    fn topology_of_a_triangle_and_an_annulus() {
+        let triangle = analyzed(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(0.0, 1.0)]);
+        assert_eq!(triangle.euler_characteristic(), 1);
+        assert_eq!(triangle.betti_numbers(), (1, 0));
+
+        let ring = annulus();
+        assert_eq!(ring.euler_characteristic(), 0);
+        assert_eq!(ring.betti_numbers(), (1, 1));
+    }
+
+    #[test]
+    fn reads_coordinates_from_a_reader() {
+        let input = "# x y\n0 0\n\n1.5,2\n  3 4  \n";
+        let xeno = Xenobalanus::from_reader(Cursor::new(input)).unwrap();
+        assert_eq!(xeno.points(), vec![(0.0, 0.0), (1.5, 2.0), (3.0, 4.0)]);
+
+        match Xenobalanus::from_reader(Cursor::new("0 0\n1 x\n")) {
+            Err(XenoError::Parse { line, .. }) => assert_eq!(line, 2),
+            _ => panic!("malformed line was accepted"),
+        }
+    }
+
+    #[test]
+    fn grid_edges_concentrate_in_two_bearing_bins() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..10 * 10).map(|i| Point::new((i % 10) as f32 * 1.0, (i / 10) as f32 * 1.0)).collect());
+        xeno.delaunay();
+        xeno.preprocess(0, false);
+        let mut histogram = xeno.edge_bearing_histogram(8);
+        let axis_aligned = histogram[0] + histogram[4];
+        histogram.sort_unstable();
+        assert_eq!(histogram[6] + histogram[7], axis_aligned);
+        assert!(histogram[5] < histogram[6]);
+    }
+
+    #[test]
+    fn clip_to_bounds_removes_outliers() {
+        let mut xeno = Xenobalanus::new();
+        let mut points = grid_with_hole(5, Point::new(-10.0, -10.0), 0.0, 10);
+        points.insert(3, Point::new(-9999.0, 0.0));
+        points.push(Point::new(2.0, f32::MAX));
+        xeno.set_points(points);
+        xeno.set_scalars((0..27).map(|i| i as f32).collect());
+        assert_eq!(xeno.clip_to_bounds((-1.0, -1.0), (5.0, 5.0)), 2);
+        assert_eq!(xeno.points().len(), 25);
+        assert!(xeno.points().iter().all(|&(x, y)| x.abs() < 5.0 && y.abs() < 5.0));
+        assert_eq!(xeno.scalars().len(), 25);
+        assert!(!xeno.scalars().contains(&3.0) && !xeno.scalars().contains(&26.0));
+    }
+
+    #[test]
+    fn linear_cluster_is_elongated() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..20).map(|i| Point::new(i as f32, 0.1 * (i % 2) as f32)).collect());
+        let shape = xeno.cluster_shape(&(0..20).collect::<Vec<usize>>());
+        assert!(shape.elongation > 100.0);
+        assert!(shape.compactness < 0.1);
+        assert!(shape.principal_axis_bearing < 1.0 || shape.principal_axis_bearing > 179.0);
+    }
+
+    // Points spread through the unit ball, rejection-sampled from the enclosing cube
+    fn ball_points(n: usize, seed: u64) -> Vec<Point3> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut points = Vec::new();
+        while points.len() < n {
+            let p = Point3::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0));
+            if p.dot(p) <= 1.0 {
+                points.push(p);
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn concave_hull_3d_encloses_the_ball() {
+        let points = ball_points(200, 121);
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points_3d(points.clone());
+        let faces = xeno.concave_hull_3d((0..200).collect(), 0.0);
+
+        // Closed and consistently wound: every directed edge is met once, and in reverse
+        let mut directed: HashSet<(usize, usize)> = HashSet::new();
+        for f in &faces {
+            for (a, b) in [(f[0], f[1]), (f[1], f[2]), (f[2], f[0])] {
+                assert!(directed.insert((a, b)));
+            }
+        }
+        assert!(directed.iter().all(|&(a, b)| directed.contains(&(b, a))));
+
+        // Outward faces: no point lies in front of any face
+        for f in &faces {
+            let normal = (points[f[1]] - points[f[0]]).cross(points[f[2]] - points[f[0]]);
+            assert!(points.iter().all(|&p| normal.dot(p - points[f[0]]) < 1e-4));
+        }
+        let volume: f32 = faces.iter().map(|f| points[f[0]].dot(points[f[1]].cross(points[f[2]])) / 6.0).sum();
+        assert!(volume > 2.5 && volume < 4.0 / 3.0 * std::f32::consts::PI, "volume {}", volume);
+
+        // Small circumspheres only: the surface shrinks and no longer holds the whole volume
+        let carved = xeno.concave_hull_3d((0..200).collect(), 4.0);
+        let carved_volume: f32 = carved.iter().map(|f| points[f[0]].dot(points[f[1]].cross(points[f[2]])) / 6.0).sum();
+        assert!(!carved.is_empty() && carved_volume < volume);
+    }
+
+    // Each undirected edge of a closed surface belongs to exactly two faces
+    fn is_closed(faces: &[[usize; 3]]) -> bool {
+        let mut edge_counts: HashMap<Edge, usize> = HashMap::new();
+        for face in faces {
+            for (a, b) in triangle_edges(face) {
+                *edge_counts.entry(Edge(min(a, b), max(a, b))).or_default() += 1;
+            }
+        }
+        !faces.is_empty() && edge_counts.values().all(|&count| count == 2)
+    }
+
+    #[test]
+    fn random_cloud_tetrahedralizes_into_empty_spheres() {
+        let mut rng = StdRng::seed_from_u64(121);
+        let points: Vec<Point3> = (0..300)
+            .map(|_| Point3::new(rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0)))
+            .collect();
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points_3d(points.clone());
+        xeno.delaunay_3d();
+        let tetrahedra = xeno.tetrahedra.len() / 4;
+        assert!(tetrahedra > 300);
+        for t in xeno.tetrahedra.chunks_exact(4) {
+            let (center, r2) = circumsphere_f64(points[t[0]], points[t[1]], points[t[2]], points[t[3]]).unwrap();
+            let inside = points.iter().filter(|p| {
+                (p.x as f64 - center[0]).powi(2) + (p.y as f64 - center[1]).powi(2) + (p.z as f64 - center[2]).powi(2) < r2 * (1.0 - 1e-9)
+            });
+            assert_eq!(inside.count(), 0, "tetrahedron {:?}", t);
+        }
+        assert!(is_closed(&xeno.boundary_faces()));
+        assert!(is_closed(&xeno.concave_hull_3d((0..300).collect(), 0.0)));
+        // The tetrahedra tile the convex hull, whose volume the surface encloses as well
+        let enclosed: f32 = xeno.boundary_faces().iter().map(|f| points[f[0]].dot(points[f[1]].cross(points[f[2]])) / 6.0).sum();
+        assert!((xeno.void_volume_3d(&(0..tetrahedra).collect()) - enclosed).abs() < 1e-4);
+    }
+
+    #[test]
+    fn cospherical_lattice_tetrahedralizes_into_the_box() {
+        // Every cell of the 4x4x4 lattice has eight cospherical corners
+        let mut points: Vec<Point3> = (0..64).map(|i| Point3::new((i % 4) as f32, (i / 4 % 4) as f32, (i / 16) as f32)).collect();
+        // A duplicate of lattice point 57 is skipped
+        points.push(Point3::new(1.0, 2.0, 3.0));
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points_3d(points);
+        xeno.delaunay_3d();
+        let tetrahedra = xeno.tetrahedra.len() / 4;
+        assert!((xeno.void_volume_3d(&(0..tetrahedra).collect()) - 27.0).abs() < 1e-4);
+        assert!(xeno.tetrahedra.iter().all(|&v| v < 64));
+
+        // Two triangles for each of the 6 * 9 unit squares on the box surface
+        let faces = xeno.concave_hull_3d((0..64).collect(), 0.0);
+        assert_eq!(faces.len(), 108);
+        assert!(is_closed(&faces));
+        assert!(is_closed(&xeno.boundary_faces()));
+
+        xeno.set_points_3d((0..16).map(|i| Point3::new((i % 4) as f32, (i / 4) as f32, 0.0)).collect());
+        xeno.delaunay_3d();
+        assert!(xeno.tetrahedra.is_empty());
+    }
+
+    #[test]
+    fn concave_hull_3d_ignores_points_outside_the_subset() {
+        let mut points = ball_points(100, 122);
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points_3d(points.clone());
+        let subset: Vec<usize> = (0..100).filter(|&i| points[i].x < 0.0).collect();
+        let faces = xeno.concave_hull_3d(subset.clone(), 0.0);
+        assert!(!faces.is_empty());
+        assert!(faces.iter().flatten().all(|v| subset.contains(v)));
+
+        points.extend(ball_points(100, 123));
+        xeno.set_points_3d(points);
+        assert_eq!(xeno.concave_hull_3d(subset, 0.0), faces);
+    }
+
+    #[test]
+    fn scalar_gradient_recovers_a_linear_field() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..6 * 6)
+            .map(|i| Point::new(((i % 6) as f32 + (i / 6 % 2) as f32 / 2.0) * 1.0, (i / 6) as f32 * 1.0 * 3f32.sqrt() / 2.0))
+            .collect());
+        xeno.delaunay();
+        assert!(matches!(xeno.scalar_gradient(0), Err(XenoError::ScalarsMismatch { scalars: 0, points: 36 })));
+
+        let field: Vec<f32> = xeno.points().iter().map(|&(x, y)| 2.0 * x - 3.0 * y + 1.0).collect();
+        xeno.set_scalars(field);
+        for index in 0..xeno.triangles_flat().len() / 3 {
+            let (gx, gy) = xeno.scalar_gradient(index).unwrap();
+            assert!((gx - 2.0).abs() < 1e-4 && (gy + 3.0).abs() < 1e-4, "({}, {}) at {}", gx, gy, index);
+        }
+    }
+
+    #[test]
+    fn trim_boundary_peels_off_needles() {
+        // A far outlier adds a fan of needles to the right of the grid
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..5 * 5).map(|i| Point::new((i % 5) as f32 * 1.0, (i / 5) as f32 * 1.0)).collect());
+        xeno.add_points(vec![Point::new(10.0, 2.0)]);
+        xeno.delaunay();
+        xeno.preprocess(0, false);
+        let before = xeno.triangulated_area().unwrap();
+
+        xeno.trim_boundary(3.0);
+        xeno.preprocess(0, false);
+        let after = xeno.triangulated_area().unwrap();
+        assert!(after < before);
+        assert!((after - 16.0).abs() < 1e-4);
+        assert!(!xeno.triangulation_boundary().contains(&25));
+    }
+
+    #[test]
+    fn svg_has_a_path_per_void() {
+        let xeno = analyzed(grid_with_hole(20, Point::new(10.0, 10.0), 4.0, 12));
+        let voids = xeno.delfin(5.0, 2.0).unwrap();
+        let clusters = xeno.dtscan(3, 1.6);
+        let svg = xeno.to_svg(&voids, &clusters, 400, 300, 2);
+
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+        assert!(!voids.is_empty());
+        assert_eq!(svg.matches("<path fill-rule=\"evenodd\"").count(), voids.len());
+        assert!(svg.lines().skip(1).filter(|l| l.starts_with('<') && !l.starts_with("</")).all(|l| l.ends_with("/>") || l.starts_with("<g ")));
+
+        // The annulus is a single path: the outer ring and the hole as a second subpath
+        let annulus = annulus();
+        let svg = annulus.to_svg(&[(0..8).collect()], &[], 300, 300, 0);
+        let paths: Vec<&str> = svg.lines().filter(|l| l.starts_with("<path")).collect();
+        assert_eq!(paths, vec!["<path fill-rule=\"evenodd\" d=\"M0,300 L300,300 L300,0 L0,0 Z M100,200 L100,100 L200,100 L200,200 Z\"/>"]);
+    }
+
+    #[test]
+    fn clipping_a_hull_touching_void() {
+        // Hole on the left edge, so the void reaches the hull
+        let xeno = analyzed(grid_with_hole(20, Point::new(0.0, 10.0), 5.0, 13));
+        let void = xeno.largest_void(2.0).unwrap().unwrap();
+        let raw = xeno.void_area(&void);
+
+        // The triangulation fills the hull, so clipping to it keeps the whole void
+        let hull_clipped = xeno.clip_void_to_hull(&void).unsigned_area();
+        assert!((hull_clipped - raw).abs() < 1e-3 * raw);
+
+        let domain = Polygon::new(LineString::from(vec![(2.0, -1.0), (20.0, -1.0), (20.0, 20.0), (2.0, 20.0), (2.0, -1.0)]), Vec::new());
+        let clipped = xeno.clip_void_to_domain(&void, &domain).unsigned_area();
+        assert!(clipped > 0.0 && clipped < raw);
+    }
+
+    #[test]
+    fn chunked_delaunay_matches_the_monolithic_one() {
+        let points = grid_with_hole(40, Point::new(20.0, 20.0), 6.0, 14);
+        let triangles = |xeno: &Xenobalanus| -> HashSet<[usize; 3]> {
+            xeno.triangles_flat().chunks(3)
+                .map(|t| {
+                    let mut key = [t[0], t[1], t[2]];
+                    key.sort_unstable();
+                    key
+                })
+                .collect()
+        };
+
+        let mono = analyzed(points.clone());
+        let mut chunked = Xenobalanus::new();
+        chunked.set_points(points);
+        chunked.delaunay_chunked((3, 3));
+        chunked.preprocess(0, false);
+        assert_eq!(triangles(&chunked), triangles(&mono));
+
+        let total = |xeno: &Xenobalanus| xeno.delfin(1.0, 1.5).unwrap().iter().map(|v| xeno.void_area(v)).sum::<f32>();
+        assert!((total(&chunked) - total(&mono)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn relational_maps_are_populated_by_preprocess() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(grid_with_hole(5, Point::new(-10.0, -10.0), 0.0, 15));
+        xeno.delaunay();
+        assert!(xeno.edge_to_triangles().is_empty() && xeno.vertex_connections().is_empty());
+
+        xeno.preprocess(0, false);
+        assert_eq!(xeno.vertex_connections().len(), 25);
+        assert_eq!(xeno.edge_to_triangles().len(), xeno.edge_lengths().len());
+        assert!(xeno.edge_to_triangles().values().all(|t| t.len() == 1 || t.len() == 2));
+    }
+
+    #[test]
+    fn parallel_and_sequential_areas_are_identical() {
+        let points = grid_with_hole(15, Point::new(-10.0, -10.0), 0.0, 16);
+        let mut sequential = Xenobalanus::new();
+        sequential.set_points(points.clone());
+        sequential.delaunay();
+        sequential.preprocess(0, false);
+        let mut parallel = Xenobalanus::new();
+        parallel.set_points(points);
+        parallel.delaunay();
+        parallel.preprocess(0, true);
+
+        for ((_, s), (_, p)) in sequential.iter_triangles().zip(parallel.iter_triangles()) {
+            assert_eq!(s.area.unwrap().to_bits(), p.area.unwrap().to_bits());
+            let v = &s.vertices;
+            let expected = Point::signed_area(sequential.point(v[0]), sequential.point(v[1]), sequential.point(v[2])).abs();
+            assert!((s.area.unwrap() - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn medial_axis_of_an_elongated_void_runs_along_its_center() {
+        let mut points = grid_with_hole(30, Point::new(-10.0, -10.0), 0.0, 17);
+        points.retain(|p| !(p.x > 6.0 && p.x < 24.0 && p.y > 12.5 && p.y < 16.5));
+        let xeno = analyzed(points);
+        let void = xeno.largest_void(2.0).unwrap().unwrap();
+        let axis = xeno.medial_axis(&void);
+
+        assert!(axis.len() >= 5);
+        let ends: Vec<Point> = axis.iter().flat_map(|&(a, b)| [a, b]).collect();
+        let mean_offset = ends.iter().map(|p| (p.y - 14.5).abs()).sum::<f32>() / ends.len() as f32;
+        assert!(mean_offset < 1.0, "mean offset {}", mean_offset);
+        let (lo, hi) = ends.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| (lo.min(p.x), hi.max(p.x)));
+        assert!(hi - lo > 4.0);
+    }
+
+    #[test]
+    fn preallocated_buffers_are_filled_in_place() {
+        let mut sized = Xenobalanus::with_capacity(144);
+        let (buffer, capacity) = (sized.points.as_ptr(), sized.points.capacity());
+        sized.lattice_points(12, 12, 1.0, false);
+        assert_eq!((sized.points.as_ptr(), sized.points.capacity()), (buffer, capacity));
+
+        sized.reserve(100);
+        let (buffer, capacity) = (sized.points.as_ptr(), sized.points.capacity());
+        sized.random_points((5.5, 5.5), 11.0, 100);
+        assert_eq!((sized.points.as_ptr(), sized.points.capacity()), (buffer, capacity));
+
+        // A triangulation of n points has fewer than 3n edges, so the tables never grow
+        let edge_capacity = sized.geometry_data.edge_lengths.capacity();
+        sized.delaunay();
+        sized.preprocess(0, false);
+        assert_eq!(sized.geometry_data.edge_lengths.capacity(), edge_capacity);
+
+        let plain = analyzed(sized.points.clone());
+        assert_eq!(sized.triangles_flat(), plain.triangles_flat());
+        assert_eq!(sized.triangulated_area().unwrap(), plain.triangulated_area().unwrap());
+        assert_eq!(sized.delfin(0.5, 1.0).unwrap().len(), plain.delfin(0.5, 1.0).unwrap().len());
+    }
+
+    #[test]
+    fn round_void_is_more_compact_than_a_strip() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..20 * 20)
+            .map(|i| Point::new(((i % 20) as f32 + (i / 20 % 2) as f32 / 2.0) * 1.0, (i / 20) as f32 * 1.0 * 3f32.sqrt() / 2.0))
+            .collect());
+        xeno.delaunay();
+        xeno.preprocess(0, false);
+        let select = |keep: &dyn Fn(Point) -> bool| -> HashSet<usize> {
+            xeno.iter_triangles()
+                .filter(|(_, t)| keep(centroid_of(&xeno, &t.vertices)))
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let center = centroid_of(&xeno, &(0..400).collect::<Vec<usize>>());
+        let round = select(&|p| p.distance(center) < 5.0);
+        let strip = select(&|p| (p.y - center.y).abs() < 0.5 && (p.x - center.x).abs() < 7.0);
+
+        let values = xeno.void_compactness(&[round, strip]);
+        assert!(values[0] > 0.6 && values[0] <= 1.0, "round void {}", values[0]);
+        assert!(values[1] < 0.5 * values[0], "strip {}", values[1]);
+    }
+
+    #[test]
+    fn flat_layout_reconstructs_the_sets() {
+        let xeno = analyzed(grid_with_hole(20, Point::new(10.0, 10.0), 4.0, 20));
+        let voids = xeno.delfin(0.0, 1.0).unwrap();
+        let (flat, offsets) = xeno.voids_flat(&voids);
+        assert_eq!(offsets.len(), voids.len() + 1);
+        assert_eq!(*offsets.last().unwrap(), flat.len());
+        for (i, void) in voids.iter().enumerate() {
+            let rebuilt: HashSet<usize> = flat[offsets[i]..offsets[i + 1]].iter().copied().collect();
+            assert_eq!(&rebuilt, void);
+        }
+
+        let clusters = vec![vec![4, 1, 7], Vec::new(), vec![2]];
+        let (flat, offsets) = xeno.clusters_flat(&clusters);
+        assert_eq!((flat, offsets), (vec![4, 1, 7, 2], vec![0, 3, 3, 4]));
+    }
+
+    #[test]
+    fn min_triangles_filters_small_voids() {
+        let mut xeno = analyzed(grid_with_hole(20, Point::new(-10.0, -10.0), 0.0, 21));
+        assert_eq!(xeno.min_triangles(), 3);
+        let default = xeno.delfin(0.0, 1.0).unwrap();
+        assert!(default.iter().all(|void| void.len() >= 3));
+
+        xeno.set_min_triangles(1);
+        let all = xeno.delfin(0.0, 1.0).unwrap();
+        assert!(all.len() > default.len());
+        xeno.set_min_triangles(3);
+        assert_eq!(xeno.delfin(0.0, 1.0).unwrap(), default);
+    }
+
+    // Jittered 30 x 30 grid with holes of radius 3 centered on the returned points
+    fn three_holes() -> (Xenobalanus, Vec<Point>) {
+        let holes = vec![Point::new(6.0, 6.0), Point::new(6.0, 24.0), Point::new(15.0, 6.0)];
+        let mut points = grid_with_hole(30, Point::new(-10.0, -10.0), 0.0, 22);
+        points.retain(|p| holes.iter().all(|h| p.distance(*h) >= 3.0));
+        (analyzed(points), holes)
+    }
+
+    // Index of the hole each void sits in
+    fn hole_of(xeno: &Xenobalanus, void: &HashSet<usize>, holes: &[Point]) -> usize {
+        let centroid = xeno.void_centroid(void);
+        (0..holes.len()).min_by(|&a, &b| centroid.distance(holes[a]).total_cmp(&centroid.distance(holes[b]))).unwrap()
+    }
+
+    #[test]
+    fn nearest_void_pairs() {
+        let (xeno, holes) = three_holes();
+        let voids = xeno.delfin(8.0, 2.0).unwrap();
+        assert_eq!(voids.len(), 3);
+        let ids: Vec<usize> = voids.iter().map(|v| hole_of(&xeno, v, &holes)).collect();
+
+        // Holes 0 and 2 are 9 apart, hole 1 is 18 above hole 0
+        let expected = [2, 0, 0];
+        for (i, &(j, distance)) in xeno.void_nearest_neighbor(&voids).iter().enumerate() {
+            assert_eq!(ids[j], expected[ids[i]]);
+            assert!((distance - holes[ids[i]].distance(holes[ids[j]])).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn sphere_points_lie_on_the_sphere() {
+        let mut xeno = Xenobalanus::new();
+        xeno.random_points_sphere((1.0, -2.0, 3.0), 5.0, 500, 23);
+        let points = xeno.points_3d();
+        assert_eq!(points.len(), 500);
+        let center = Point3::new(1.0, -2.0, 3.0);
+        assert!(points.iter().all(|&(x, y, z)| (Point3::new(x, y, z).distance(center) - 5.0).abs() < 1e-4));
+        // Uniform in z, so no pile-up at the poles
+        let polar = points.iter().filter(|p| (p.2 - 3.0).abs() > 4.0).count();
+        assert!((70..=130).contains(&polar), "{} points near the poles", polar);
+    }
+
+    #[test]
+    fn mesh_report_of_a_square_grid() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..4 * 4).map(|i| Point::new((i % 4) as f32 * 1.0, (i / 4) as f32 * 1.0)).collect());
+        xeno.delaunay();
+        let report = xeno.mesh_report();
+        assert_eq!((report.n_points, report.n_triangles, report.n_edges, report.n_boundary_edges), (16, 18, 33, 12));
+        assert!((report.min_angle - 45.0).abs() < 1e-3 && (report.max_angle - 90.0).abs() < 1e-3);
+        assert!((report.min_area - 0.5).abs() < 1e-6 && (report.max_area - 0.5).abs() < 1e-6);
+        assert_eq!(report.n_sliver_triangles, 0);
+    }
+
+    #[test]
+    fn holes_stay_empty_and_become_voids() {
+        let hole = (Point::new(5.0, 5.0), 2.0);
+        let mut xeno = Xenobalanus::new();
+        xeno.random_points_with_holes((5.0, 5.0), 10.0, 800, &[hole], 24).unwrap();
+        assert_eq!(xeno.points().len(), 800);
+        assert!(xeno.points().iter().all(|&(x, y)| Point::new(x, y).distance(hole.0) >= hole.1));
+
+        xeno.delaunay();
+        xeno.preprocess(0, false);
+        let void = xeno.largest_void(0.8).unwrap().unwrap();
+        assert!(xeno.void_centroid(&void).distance(hole.0) < 0.5);
+
+        let mut covered = Xenobalanus::new();
+        let result = covered.random_points_with_holes((0.0, 0.0), 1.0, 5, &[(Point::new(0.0, 0.0), 1.0)], 24);
+        assert!(matches!(result, Err(XenoError::SamplingExhausted { generated: 0, requested: 5 })));
+    }
+
+    #[test]
+    fn streamed_geojson_is_a_feature_collection() {
+        let (xeno, _) = three_holes();
+        let voids = xeno.delfin(8.0, 2.0).unwrap();
+        let mut buffer: Vec<u8> = Vec::new();
+        xeno.write_voids_geojson(&voids, &mut buffer, 3).unwrap();
+        let json = String::from_utf8(buffer).unwrap();
+
+        assert!(json.starts_with("{\"type\":\"FeatureCollection\",\"features\":[{") && json.ends_with("}]}"));
+        assert_eq!(json.matches("\"type\":\"Feature\"").count(), voids.len());
+        assert_eq!(json.matches('[').count(), json.matches(']').count());
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+        assert!(!json.contains(",]") && !json.contains(",}") && !json.contains("][") && !json.contains("}{"));
+
+        let mut empty: Vec<u8> = Vec::new();
+        xeno.write_voids_geojson(&[], &mut empty, 3).unwrap();
+        assert_eq!(empty, b"{\"type\":\"FeatureCollection\",\"features\":[]}");
+    }
+
+    #[test]
+    fn cached_edge_lengths_match_the_distances() {
+        let xeno = analyzed(grid_with_hole(10, Point::new(-10.0, -10.0), 0.0, 25));
+        for (edge, &length) in xeno.edge_lengths() {
+            assert_eq!(length, xeno.point(edge.0).distance(xeno.point(edge.1)));
+        }
+        assert!(xeno.iter_triangles().all(|(_, t)| t.get_edges().iter().all(|e| xeno.edge_lengths().contains_key(e))));
+    }
+
+    #[test]
+    fn barbell_has_a_single_bridge() {
+        // Two 3x3 grids touching only through the unit edge (2, 2)-(3, 2)
+        let grid = |x0: f32, y0: f32| (0..9).map(move |i| Point::new(x0 + (i % 3) as f32, y0 + (i / 3) as f32));
+        let xeno = analyzed(grid(0.0, 0.0).chain(grid(3.0, 2.0)).collect());
+        assert_eq!(xeno.bridge_edges(1.1), vec![Edge(8, 9)]);
+        // Diagonals close every cycle, the full triangulation has no bridges at all
+        assert!(xeno.bridge_edges(1.5).is_empty());
+        assert!(xeno.bridge_edges(f32::INFINITY).is_empty());
+    }
+
+    #[test]
+    fn exporters_round_to_the_requested_places() {
+        let (xeno, _) = three_holes();
+        let voids = xeno.delfin(8.0, 2.0).unwrap();
+        let mut buffer: Vec<u8> = Vec::new();
+        xeno.write_voids_geojson(&voids, &mut buffer, 2).unwrap();
+        let svg = xeno.to_svg(&voids, &[], 200, 200, 1);
+
+        let decimals = |text: &str| -> Vec<usize> {
+            text.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .filter_map(|number| number.split_once('.'))
+                .map(|(_, fraction)| fraction.len())
+                .collect()
+        };
+        let geojson = decimals(&String::from_utf8(buffer).unwrap());
+        assert!(!geojson.is_empty() && geojson.iter().all(|&d| d == 2));
+        let svg_body = svg.split_once('\n').unwrap().1;
+        assert!(decimals(svg_body).iter().all(|&d| d == 1 || d == 0));
+        assert!(!xeno.voronoi_to_geojson(0).contains('.'));
+    }
+
+    #[test]
+    fn merged_instances_can_be_reanalyzed() {
+        let mut left = Xenobalanus::new();
+        left.set_points(grid_with_hole(10, Point::new(-10.0, -10.0), 0.0, 26));
+        left.set_scalars(vec![1.0; 100]);
+        left.delaunay();
+        let mut right = Xenobalanus::new();
+        right.set_points(grid_with_hole(10, Point::new(-10.0, -10.0), 0.0, 27).iter().map(|p| Point::new(p.x + 10.0, p.y)).collect());
+        right.set_scalars(vec![2.0; 100]);
+
+        left.merge(right);
+        assert_eq!(left.points().len(), 200);
+        assert_eq!(left.scalars().len(), 200);
+        assert_eq!(left.scalars()[150], 2.0);
+        assert!(left.triangles_flat().is_empty());
+
+        left.delaunay();
+        left.preprocess(0, false);
+        assert!(left.delfin(0.0, 1.0).is_ok());
+        // Cross-region edges join the two halves after re-triangulation
+        assert!(left.edge_lengths().keys().any(|e| e.0 < 100 && e.1 >= 100));
+    }
+
+    #[test]
+    fn queries_are_assigned_to_the_closest_void() {
+        let (xeno, holes) = three_holes();
+        let voids = xeno.delfin(8.0, 2.0).unwrap();
+        let queries = [Point::new(5.0, 7.0), Point::new(7.0, 23.0), Point::new(16.0, 4.0), Point::new(12.0, 6.0)];
+        let assigned = xeno.assign_to_nearest_void(&queries, &voids);
+        let ids: Vec<usize> = assigned.iter().map(|&v| hole_of(&xeno, &voids[v], &holes)).collect();
+        assert_eq!(ids, vec![0, 1, 2, 2]);
+        assert!(xeno.assign_to_nearest_void(&queries, &[]).is_empty());
+    }
+
+    #[test]
+    fn refinement_seeds_sit_in_oversized_circumcircles() {
+        let (xeno, _) = three_holes();
+        let seeds = xeno.refinement_candidates(1.0);
+        assert!(!seeds.is_empty());
+        let hull = xeno.convex_hull();
+        let oversized: Vec<(Point, f32)> = xeno.iter_triangles()
+            .filter(|(_, t)| t.area.unwrap() > 1.0)
+            .filter_map(|(i, _)| xeno.circumcircle(i))
+            .collect();
+        for seed in seeds {
+            assert!(hull.intersects(&GeoPoint::new(seed.x, seed.y)));
+            assert!(oversized.iter().any(|&(center, _)| center.distance(seed) < 1e-4));
+        }
+    }
+
+    #[test]
+    fn tighter_cluster_is_denser() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(vec![
+            Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(1.0, 1.0), Point::new(0.0, 1.0),
+            Point::new(5.0, 0.0), Point::new(8.0, 0.0), Point::new(8.0, 3.0), Point::new(5.0, 3.0),
+        ]);
+        let density = xeno.cluster_density(&[vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![0, 1, 4]]);
+        assert!((density[0] - 4.0).abs() < 1e-5);
+        assert!(density[0] > density[1]);
+        assert_eq!(density[2], f32::INFINITY);
+    }
+
+    #[test]
+    fn dtscan_session_matches_dtscan() {
+        let mut points = grid_with_hole(15, Point::new(7.0, 7.0), 3.0, 28);
+        points.extend(grid_with_hole(6, Point::new(-10.0, -10.0), 0.0, 29).iter().map(|p| Point::new(p.x * 0.5 + 20.0, p.y * 0.5)));
+        let xeno = analyzed(points);
+        let normalized = |mut clusters: Vec<Vec<usize>>| {
+            clusters.iter_mut().for_each(|c| c.sort_unstable());
+            clusters.sort_unstable();
+            clusters
+        };
+
+        let mut session = xeno.dtscan_session();
+        // Rising and falling thresholds exercise both the incremental and the reset path
+        for &(min_pts, max_closeness) in &[(3, 0.6), (4, 1.0), (3, 1.3), (5, 1.6), (3, 0.9), (6, 3.0)] {
+            assert_eq!(normalized(session.query(min_pts, max_closeness)), normalized(xeno.dtscan(min_pts, max_closeness)));
+        }
+    }
+
+    #[test]
+    fn boundary_linestring_of_a_convex_set_is_closed() {
+        let mut points: Vec<Point> = (0..12)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::TAU / 12.0;
+                Point::new(angle.cos() * 5.0, angle.sin() * 5.0)
+            })
+            .collect();
+        points.push(Point::new(0.3, -0.2));
+        let xeno = analyzed(points);
+        let line = xeno.boundary_linestring();
+        assert_eq!(line.0.len(), 13);
+        assert!(line.is_closed());
+        assert_eq!(xeno.triangulation_boundary().len(), 12);
+    }
+
+    #[test]
+    fn collinear_input_is_degenerate() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(vec![Point::new(0.0, 0.0), Point::new(1.0, 1.0), Point::new(2.0, 2.0)]);
+        xeno.delaunay();
+        assert!(xeno.is_degenerate());
+        assert!(xeno.triangles_flat().is_empty());
+
+        xeno.add_points(vec![Point::new(2.0, 0.0)]);
+        xeno.delaunay();
+        assert!(!xeno.is_degenerate());
+    }
+
+    #[test]
+    fn imported_meshes_set_the_degenerate_flag_like_set_triangles() {
+        let points = vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(0.0, 1.0)];
+        let mut xeno = Xenobalanus::new();
+        xeno.set_mesh(points.clone(), vec![]).unwrap();
+        assert!(xeno.is_degenerate());
+        xeno.set_mesh(points, vec![0, 1, 2]).unwrap();
+        assert!(!xeno.is_degenerate());
+
+        xeno.set_triangles(vec![]);
+        assert!(xeno.is_degenerate());
+        xeno.set_triangles(vec![0, 1, 2]);
+        assert!(!xeno.is_degenerate());
+    }
+
+    #[test]
+    fn trimming_every_triangle_leaves_the_mesh_degenerate() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0), Point::new(0.5, 0.1)]);
+        xeno.delaunay();
+        assert!(!xeno.is_degenerate());
+
+        xeno.trim_boundary(3.0);
+        assert!(xeno.triangles_flat().is_empty());
+        assert!(xeno.is_degenerate());
+    }
+
+    #[test]
+    fn graph_diameter_of_a_chain_is_its_length() {
+        // Strip of triangles, the bottom row forms a chain of unit edges
+        let mut points: Vec<Point> = (0..6).map(|i| Point::new(i as f32, 0.0)).collect();
+        points.extend((0..5).map(|i| Point::new(i as f32 + 0.5, 1.0)));
+        let xeno = analyzed(points);
+        assert!((xeno.cluster_graph_diameter(&[0, 1, 2, 3, 4, 5]) - 5.0).abs() < 1e-5);
+        assert_eq!(xeno.cluster_graph_diameter(&[0]), 0.0);
+    }
+
+    #[test]
+    fn equal_length_edges_pick_a_stable_terminal_edge() {
+        // Sides 0-1 and 0-2 are both 5 long
+        let points = vec![Point::new(0.0, 0.0), Point::new(5.0, 0.0), Point::new(3.0, 4.0)];
+        for order in [[0, 1, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0], [0, 2, 1]] {
+            let mut xeno = Xenobalanus::new();
+            xeno.set_mesh(points.clone(), order.to_vec()).unwrap();
+            xeno.preprocess(0, false);
+            assert_eq!(xeno.triangle(0).terminal_edge, Some(Edge(0, 1)));
+        }
+    }
+
+    #[test]
+    fn half_masked_void_overlaps_half_its_area() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..5 * 5).map(|i| Point::new((i % 5) as f32 * 1.0, (i / 5) as f32 * 1.0)).collect());
+        xeno.delaunay();
+        xeno.preprocess(0, false);
+        let void: HashSet<usize> = xeno.iter_triangles().map(|(i, _)| i).collect();
+        let mask = Polygon::new(LineString::from(vec![(-1.0, -1.0), (2.0, -1.0), (2.0, 5.0), (-1.0, 5.0), (-1.0, -1.0)]), Vec::new());
+        assert!((xeno.void_polygon_overlap(&void, &mask) - 0.5 * xeno.void_area(&void)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn recomputed_areas_match_a_full_preprocess() {
+        let mut xeno = analyzed(grid_with_hole(10, Point::new(-10.0, -10.0), 0.0, 30));
+        let mut rng = StdRng::seed_from_u64(31);
+        for p in xeno.points.iter_mut() {
+            p.x += rng.gen_range(-0.01..0.01);
+            p.y += rng.gen_range(-0.01..0.01);
+        }
+        xeno.recompute_areas();
+        let recomputed: Vec<f32> = xeno.iter_triangles().map(|(_, t)| t.area.unwrap()).collect();
+        xeno.preprocess(0, false);
+        let full: Vec<f32> = xeno.iter_triangles().map(|(_, t)| t.area.unwrap()).collect();
+        assert_eq!(recomputed, full);
+    }
+
+    #[test]
+    fn iter_triangles_yields_only_real_triangles() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(grid_with_hole(12, Point::new(-10.0, -10.0), 0.0, 32));
+        xeno.delaunay();
+        let n_triangles = xeno.triangles_flat().len() / 3;
+
+        xeno.preprocess(1, false);
+        assert!(xeno.triangle_data().is_empty());
+        assert_eq!(xeno.iter_triangles().count(), 0);
+
+        xeno.preprocess(0, true);
+        assert_eq!(xeno.iter_triangles().count(), n_triangles);
+        assert!(xeno.iter_triangles().all(|(i, t)| t.index == i && t.vertices.len() == 3));
+    }
+
+    #[test]
+    fn parallel_insertion_leaves_no_empty_slots() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(grid_with_hole(30, Point::new(-10.0, -10.0), 0.0, 33));
+        xeno.delaunay();
+        for types in [0, 2] {
+            xeno.preprocess(types, true);
+            assert_eq!(xeno.triangle_data().len(), xeno.triangles_flat().len() / 3);
+            assert!(xeno.triangle_data().iter().enumerate().all(|(i, t)| t.index == i && t.vertices.len() == 3 && t.area.is_some()));
+        }
+    }
+
+    #[test]
+    fn poisson_expectation_is_reproducible() {
+        let xeno = analyzed(grid_with_hole(15, Point::new(-10.0, -10.0), 0.0, 34));
+        let stats = xeno.poisson_void_expectation(8, 99, 0.0, 1.0);
+        assert_eq!(stats.n_trials, 8);
+        assert!(stats.mean_max_area > 0.0 && stats.std_max_area >= 0.0);
+        assert_eq!(stats, xeno.poisson_void_expectation(8, 99, 0.0, 1.0));
+        assert_ne!(stats, xeno.poisson_void_expectation(8, 100, 0.0, 1.0));
+        assert_eq!(xeno.poisson_void_expectation(0, 99, 0.0, 1.0).n_trials, 0);
+    }
+
+    #[test]
+    fn snapshots_are_analyzed_independently() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_snapshot(0, grid_with_hole(20, Point::new(5.0, 5.0), 3.5, 35));
+        xeno.set_snapshot(1, grid_with_hole(20, Point::new(14.0, 12.0), 3.5, 36));
+
+        for (t, hole) in [(0, Point::new(5.0, 5.0)), (1, Point::new(14.0, 12.0)), (0, Point::new(5.0, 5.0))] {
+            let voids = xeno.analyze_snapshot(t, 20.0, 2.0).unwrap();
+            assert_eq!(voids.len(), 1);
+            assert!(xeno.void_centroid(&voids[0]).distance(hole) < 1.0);
+        }
+        assert!(matches!(xeno.analyze_snapshot(2, 0.0, 1.0), Err(XenoError::UnknownSnapshot(2))));
+    }
+
+    #[test]
+    fn square_outline_turns_ninety_degrees_per_corner() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(vec![Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 2.0), Point::new(0.0, 2.0), Point::new(1.0, 2.0)]);
+        assert_eq!(xeno.boundary_curvature(&[0, 1, 2, 3]), vec![90.0; 4]);
+        assert_eq!(xeno.boundary_curvature(&[3, 2, 1, 0]), vec![-90.0; 4]);
+        // A vertex in the middle of a side does not turn
+        assert_eq!(xeno.boundary_curvature(&[0, 1, 2, 4, 3]), vec![90.0, 90.0, 90.0, 0.0, 90.0]);
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    #[test]
+    fn parallel_preprocess_falls_back_to_a_single_thread() {
+        let mut xeno = analyzed(grid_with_hole(15, Point::new(7.0, 7.0), 3.0, 37));
+        let sequential = xeno.delfin(1.0, 1.5).unwrap();
+        xeno.preprocess(0, true);
+        assert_eq!(xeno.delfin(1.0, 1.5).unwrap(), sequential);
+    }
+
+    #[test]
+    fn flip_distance_counts_differing_edges() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_mesh(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(1.0, 1.0), Point::new(0.0, 1.0)], vec![0, 1, 2, 0, 2, 3]).unwrap();
+        assert_eq!(xeno.flip_distance(&[2, 0, 1, 3, 0, 2]), 0);
+        assert_eq!(xeno.flip_distance(&[0, 1, 3, 1, 2, 3]), 1);
+    }
+
+    #[test]
+    fn masked_triangles_never_join_a_void() {
+        let mut xeno = analyzed(grid_with_hole(20, Point::new(10.0, 10.0), 4.0, 38));
+        let masked: HashSet<usize> = xeno.iter_triangles()
+            .filter(|(_, t)| centroid_of(&xeno, &t.vertices).x < 10.0)
+            .map(|(i, _)| i)
+            .collect();
+        let unmasked = xeno.delfin(0.0, 1.0).unwrap();
+        assert!(unmasked.iter().any(|void| !void.is_disjoint(&masked)));
+
+        xeno.set_void_mask(masked.clone());
+        let voids = xeno.delfin(0.0, 1.0).unwrap();
+        assert!(!voids.is_empty());
+        assert!(voids.iter().all(|void| void.is_disjoint(&masked)));
+    }
+
+    #[test]
+    fn retriangulating_clears_the_void_mask() {
+        let points = grid_with_hole(10, Point::new(-10.0, -10.0), 0.0, 39);
+        let mut xeno = analyzed(points.clone());
+        xeno.set_void_mask(HashSet::from([0, 1, 2]));
+        xeno.trim_boundary(f32::MAX);
+        assert!(xeno.void_mask.is_empty());
+
+        // set_points only swaps the points, the triangulation and its mask stay
+        let triangles = xeno.triangles_flat();
+        xeno.set_void_mask(HashSet::from([0, 1, 2]));
+        xeno.set_points(points);
+        assert_eq!(xeno.triangles_flat(), triangles);
+        assert_eq!(xeno.void_mask.len(), 3);
+        xeno.delaunay();
+        assert!(xeno.void_mask.is_empty());
+    }
+
+    #[test]
+    fn concave_hull_area_of_a_convex_set() {
+        let mut points: Vec<Point> = (0..10)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::TAU / 10.0;
+                Point::new(3.0 * angle.cos(), 2.0 * angle.sin())
+            })
+            .collect();
+        points.extend([Point::new(0.5, 0.2), Point::new(-0.4, -0.3)]);
+        let xeno = analyzed(points);
+        let all: Vec<usize> = (0..12).collect();
+        let area = xeno.concave_hull_area(all, 0.0).unwrap();
+        assert!((area - xeno.convex_hull().unsigned_area()).abs() < 1e-4);
+        assert!(matches!(xeno.concave_hull_area(vec![0, 12], 0.0), Err(XenoError::VertexOutOfRange { vertex: 12, points: 12 })));
+    }
+
+    #[test]
+    fn memory_estimate_grows_with_the_point_count() {
+        let small = analyzed(grid_with_hole(10, Point::new(-10.0, -10.0), 0.0, 39));
+        let large = analyzed(grid_with_hole(30, Point::new(-10.0, -10.0), 0.0, 39));
+        assert_eq!(Xenobalanus::new().geometry_memory_bytes(), 0);
+        assert!(small.geometry_memory_bytes() > 0);
+        assert!(large.geometry_memory_bytes() > 5 * small.geometry_memory_bytes());
+    }
+
+    #[test]
+    fn anisotropic_dtscan_joins_a_stretched_structure() {
+        // Three rows of points 2 apart along x and 1 apart along y
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..60).map(|i| Point::new(2.0 * (i % 20) as f32, (i / 20) as f32)).collect());
+        xeno.delaunay();
+        xeno.preprocess(0, false);
+
+        let isotropic = xeno.dtscan(3, 1.5);
+        assert!(isotropic.iter().all(|cluster| cluster.len() < 60));
+        let stretched = xeno.dtscan_anisotropic(3, 1.5, (2.0, 1.0));
+        assert_eq!(stretched.len(), 1);
+        assert_eq!(stretched[0].len(), 60);
+    }
+
+    #[test]
+    fn voronoi_geojson_has_a_cell_per_site() {
+        let xeno = analyzed(grid_with_hole(6, Point::new(-10.0, -10.0), 0.0, 40));
+        let json = xeno.voronoi_to_geojson(4);
+        assert!(json.starts_with("{\"type\":\"FeatureCollection\",\"features\":[") && json.ends_with("]}"));
+        assert_eq!(json.matches("\"type\":\"Feature\"").count(), 36);
+        for site in 0..36 {
+            assert!(json.contains(&format!("\"properties\":{{\"site\":{}}}", site)));
+        }
+        assert_eq!(json.matches('[').count(), json.matches(']').count());
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+    }
+
+    #[test]
+    fn weighted_lloyd_moves_points_towards_the_peak() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..10 * 10).map(|i| Point::new((i % 10) as f32 * 1.0, (i / 10) as f32 * 1.0)).collect());
+        xeno.lloyd_relax_weighted(10, |p| (-((p.x - 7.0).powi(2) + (p.y - 7.0).powi(2)) / 4.0).exp());
+
+        let points = xeno.points();
+        let n = points.len() as f32;
+        let mean_x = points.iter().map(|p| p.0).sum::<f32>() / n;
+        let mean_y = points.iter().map(|p| p.1).sum::<f32>() / n;
+        assert!(mean_x > 4.8 && mean_y > 4.8, "mean ({}, {}) did not move towards the peak", mean_x, mean_y);
+        assert!(points.iter().all(|&(x, y)| (0.0..=9.0).contains(&x) && (0.0..=9.0).contains(&y)));
+    }
+
+    #[test]
+    fn non_manifold_fan_is_reported() {
+        // Three flat triangles on the edge 0-1, which is the longest edge of each
+        let points = vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(0.5, 0.3), Point::new(0.5, -0.3), Point::new(0.5, 0.2)];
+        let mut xeno = Xenobalanus::new();
+        xeno.set_mesh(points, vec![0, 1, 2, 0, 1, 3, 0, 1, 4]).unwrap();
+        xeno.preprocess(0, false);
+        assert_eq!(xeno.check_manifold(), vec![Edge(0, 1)]);
+        assert!(xeno.delfin(0.0, 0.0).is_ok());
+
+        // The edge is a wall, no void joins triangles on different sides of it
+        xeno.set_min_triangles(1);
+        let voids = xeno.delfin(0.0, 0.0).unwrap();
+        assert!(!voids.is_empty());
+        assert!(voids.iter().all(|void| void.len() == 1));
+
+        assert!(analyzed(grid_with_hole(5, Point::new(-10.0, -10.0), 0.0, 41)).check_manifold().is_empty());
+    }
+
+    #[test]
+    fn remote_point_is_the_most_isolated() {
+        let mut points = grid_with_hole(10, Point::new(-10.0, -10.0), 0.0, 42);
+        points.insert(17, Point::new(30.0, 30.0));
+        assert_eq!(analyzed(points).most_isolated_point(), Some(17));
+        assert_eq!(Xenobalanus::new().most_isolated_point(), None);
+    }
+
+    #[test]
+    fn delfin_needs_areas() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(grid_with_hole(5, Point::new(-10.0, -10.0), 0.0, 43));
+        xeno.delaunay();
+        xeno.preprocess(1, false);
+        assert!(matches!(xeno.delfin(0.0, 1.0), Err(XenoError::AreasNotComputed)));
+        xeno.preprocess(2, false);
+        assert!(xeno.delfin(0.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn square_outline_is_sampled_evenly() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(vec![Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(4.0, 4.0), Point::new(0.0, 4.0)]);
+        let samples = xeno.resample_boundary(&[0, 1, 2, 3], 1.0);
+        assert_eq!(samples.len(), 16);
+        for (i, pair) in samples.iter().zip(samples.iter().cycle().skip(1)).enumerate() {
+            assert!((pair.0.distance(*pair.1) - 1.0).abs() < 1e-5, "gap after sample {}", i);
+        }
+        let closed = xeno.resample_boundary(&[0, 1, 2, 3, 0], 1.0);
+        assert!(closed.iter().zip(&samples).all(|(a, b)| (a.x, a.y) == (b.x, b.y)));
+        // Spacing longer than a side carries over the corners
+        assert_eq!(xeno.resample_boundary(&[0, 1, 2, 3], 6.0).len(), 3);
+    }
+
+    #[test]
+    fn pair_counts_match_brute_force_and_the_uniform_expectation() {
+        let mut rng = StdRng::seed_from_u64(44);
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..1000).map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0))).collect());
+        let bins = [0.0, 0.25, 0.5, 0.75];
+        let counts = xeno.pair_counts(&bins);
+
+        let points = xeno.points();
+        let mut brute = vec![0; 3];
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                let d = Point::new(points[i].0, points[i].1).distance(Point::new(points[j].0, points[j].1));
+                if d < 0.75 {
+                    brute[(d / 0.25) as usize] += 1;
+                }
+            }
+        }
+        assert_eq!(counts, brute);
+
+        // Pair separations in an L x L square have the density
+        // 2 pi r / L^2 (1 - 4r / (pi L) + r^2 / (pi L^2)), the factor counting the
+        // pairs lost at the edges; integrated over each bin
+        let side = 10.0f32;
+        let cumulative = |r: f32| {
+            let x = r / side;
+            std::f32::consts::PI * x * x - 8.0 / 3.0 * x.powi(3) + x.powi(4) / 2.0
+        };
+        for (k, &count) in counts.iter().enumerate() {
+            let expected = 999.0 * 500.0 * (cumulative(bins[k + 1]) - cumulative(bins[k]));
+            assert!((count as f32 / expected - 1.0).abs() < 0.03, "bin {}: {} vs {}", k, count, expected);
+        }
+    }
+
+    #[test]
+    fn collapsed_mesh_has_no_short_edges() {
+        let mut rng = StdRng::seed_from_u64(45);
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points((0..400).map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0))).collect());
+        xeno.set_scalars(vec![1.0; 400]);
+        let collapses = xeno.collapse_short_edges(0.3);
+        assert!(collapses > 0);
+        assert_eq!(xeno.points().len(), 400 - collapses);
+        assert_eq!(xeno.scalars(), vec![1.0; 400 - collapses].as_slice());
+
+        xeno.preprocess(0, false);
+        assert!(xeno.edge_lengths().values().all(|&length| length >= 0.3));
+    }
+
+    #[test]
+    fn triangles_are_labeled_void_cluster_or_background() {
+        let xeno = analyzed(grid_with_hole(20, Point::new(12.0, 12.0), 4.0, 46));
+        let void = xeno.largest_void(2.0).unwrap().unwrap();
+        let cluster: Vec<usize> = (0..xeno.points().len()).filter(|&v| xeno.point(v).x < 5.0).collect();
+        let classes = xeno.segment_triangles(std::slice::from_ref(&void), &[cluster]);
+        let near = |p: Point| -> Vec<usize> {
+            xeno.iter_triangles()
+                .filter(|(_, t)| centroid_of(&xeno, &t.vertices).distance(p) <= 1.0)
+                .map(|(i, _)| i)
+                .collect()
+        };
+
+        assert!(void.iter().all(|&i| classes[i] == TriangleClass::Void(0)));
+        assert!(near(Point::new(2.0, 10.0)).iter().all(|&i| classes[i] == TriangleClass::Cluster(0)));
+        assert!(near(Point::new(16.0, 3.0)).iter().all(|&i| classes[i] == TriangleClass::Background));
+    }
+
+    #[test]
+    fn halfedges_pair_up() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(grid_with_hole(8, Point::new(-10.0, -10.0), 0.0, 47));
+        xeno.delaunay();
+        let halfedges = xeno.halfedges();
+        assert_eq!(halfedges.len(), xeno.triangles_flat().len());
+        for (e, &opposite) in halfedges.iter().enumerate() {
+            if opposite != delaunator::EMPTY {
+                assert_eq!(halfedges[opposite], e);
+            }
+        }
+    }
+
+    #[test]
+    fn triangles_near_matches_a_scan() {
+        let xeno = analyzed(grid_with_hole(15, Point::new(-10.0, -10.0), 0.0, 48));
+        let (p, r) = (Point::new(6.3, 8.1), 2.5);
+        let near = xeno.triangles_near(p, r);
+        let scanned: Vec<usize> = xeno.iter_triangles()
+            .filter(|(_, t)| centroid_of(&xeno, &t.vertices).distance(p) <= r)
+            .map(|(i, _)| i)
+            .collect();
+        assert!(!near.is_empty());
+        assert_eq!(near, scanned);
+    }
+
+    #[test]
+    fn alpha_values_of_gabriel_and_blocked_edges() {
+        let mut square = Xenobalanus::new();
+        square.set_mesh(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(1.0, 1.0), Point::new(0.0, 1.0)], vec![0, 1, 2, 0, 2, 3]).unwrap();
+        let alphas = square.edge_alpha_values();
+        assert_eq!(alphas.len(), 5);
+        assert!((alphas[&Edge(0, 1)] - 0.5).abs() < 1e-6);
+        assert!((alphas[&Edge(0, 2)] - 0.5f32.sqrt()).abs() < 1e-6);
+
+        // The apex lies inside the diametral circle of the long edge
+        let mut obtuse = Xenobalanus::new();
+        obtuse.set_mesh(vec![Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(2.0, 1.0)], vec![0, 1, 2]).unwrap();
+        assert!((obtuse.edge_alpha_values()[&Edge(0, 1)] - 2.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn barrier_splits_a_void() {
+        let mut xeno = analyzed(grid_with_hole(20, Point::new(10.0, 10.0), 4.0, 49));
+        xeno.set_min_triangles(1);
+        let void = xeno.largest_void(2.0).unwrap().unwrap();
+        let mut members: Vec<usize> = void.iter().copied().collect();
+        members.sort_unstable();
+        // The void grows across terminal edges, so a barrier on one cuts off the
+        // triangles behind it. Their root reseeds as long as that edge reaches min_distance.
+        let (edge, pair) = members.into_iter()
+            .find_map(|i| {
+                let edge = xeno.triangle(i).terminal_edge?;
+                let pair = xeno.edge_to_triangles()[&edge].clone();
+                (pair.len() == 2 && pair.iter().all(|t| void.contains(t)) && xeno.edge_lengths()[&edge] >= 2.0)
+                    .then_some((edge, pair))
+            })
+            .unwrap();
+        let before = xeno.delfin(0.0, 2.0).unwrap().len();
+
+        xeno.set_barriers(vec![edge]);
+        let voids = xeno.delfin(0.0, 2.0).unwrap();
+        assert_eq!(voids.len(), before + 1);
+        assert!(voids.iter().all(|v| !(v.contains(&pair[0]) && v.contains(&pair[1]))));
+    }
+
+    #[test]
+    fn largest_empty_circle_lands_in_the_hole() {
+        let xeno = analyzed(grid_with_hole(20, Point::new(8.0, 11.0), 4.0, 50));
+        let (center, radius) = xeno.largest_empty_circle();
+        assert!(center.distance(Point::new(8.0, 11.0)) < 1.0);
+        assert!(radius > 3.5);
+        assert!(Xenobalanus::new().largest_empty_circle().0.x.is_nan());
+    }
+
+    #[test]
+    fn cluster_edges_are_the_induced_subgraph() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_mesh(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(1.0, 1.0), Point::new(0.0, 1.0)], vec![0, 1, 2, 0, 2, 3]).unwrap();
+        xeno.preprocess(0, false);
+        assert_eq!(xeno.cluster_edges(&[2, 0, 1]), vec![Edge(0, 1), Edge(0, 2), Edge(1, 2)]);
+        assert!(xeno.cluster_edges(&[1, 3]).is_empty());
+    }
+
+    #[test]
+    fn signed_distance_is_negative_inside_voids() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_mesh(vec![Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 2.0), Point::new(0.0, 2.0)], vec![0, 1, 2, 0, 2, 3]).unwrap();
+        xeno.preprocess(0, false);
+        let voids = vec![HashSet::from([0, 1])];
+        assert!((xeno.void_signed_distance(Point::new(1.0, 0.5), &voids) + 0.5).abs() < 1e-6);
+        assert!((xeno.void_signed_distance(Point::new(3.0, 1.0), &voids) - 1.0).abs() < 1e-6);
+        assert_eq!(xeno.void_signed_distance(Point::new(1.0, 1.0), &[]), f32::INFINITY);
+    }
+
+    #[test]
+    fn edge_list_has_every_unique_edge_once() {
+        let xeno = analyzed(grid_with_hole(8, Point::new(-10.0, -10.0), 0.0, 51));
+        let list = xeno.graph_edge_list();
+        assert_eq!(list.len(), xeno.mesh_report().n_edges);
+        assert!(list.windows(2).all(|w| (w[0].0, w[0].1) < (w[1].0, w[1].1)));
+        assert!(list.iter().all(|&(u, v, length)| u < v && length == xeno.point(u).distance(xeno.point(v))));
+
+        let dot = xeno.graph_to_dot(2);
+        assert!(dot.starts_with("graph xenobalanus {\n") && dot.ends_with("}\n"));
+        assert_eq!(dot.matches(" -- ").count(), list.len());
+        let (u, v, length) = list[0];
+        assert!(dot.contains(&format!("  {} -- {} [weight={:.2}];\n", u, v, length)));
+    }
+
+    #[test]
+    fn strips_cover_every_triangle_once() {
+        let xeno = analyzed(grid_with_hole(12, Point::new(6.0, 6.0), 2.5, 52));
+        let strips = xeno.triangle_strips();
+        let mut covered: Vec<usize> = strips.iter().flatten().copied().collect();
+        covered.sort_unstable();
+        assert_eq!(covered, xeno.iter_triangles().map(|(i, _)| i).collect::<Vec<usize>>());
+
+        let edges = |i: usize| xeno.triangle(i).edges;
+        for strip in &strips {
+            assert!(strip.windows(2).all(|w| edges(w[0]).iter().any(|e| edges(w[1]).contains(e))));
+        }
+    }
+
+    #[test]
+    fn repaired_hull_is_simple() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(vec![Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 2.0), Point::new(0.0, 2.0), Point::new(1.0, 3.0)]);
+        let tangled = vec![0, 2, 1, 4, 3];
+        assert!(!xeno.is_simple_polygon(&tangled));
+        let mut repaired = xeno.repair_hull(tangled);
+        assert!(xeno.is_simple_polygon(&repaired));
+        repaired.sort_unstable();
+        assert_eq!(repaired, vec![0, 1, 2, 3, 4]);
+        assert_eq!(xeno.repair_hull(vec![0, 1, 2, 3]), vec![0, 1, 2, 3]);
+    }
+
+    // Octahedron with vertices at distance `stretch` along z and 1 along x and y,
+    // split into eight tetrahedra around its center (vertex 6)
+    fn octahedron(stretch: f32) -> Xenobalanus {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points_3d(vec![
+            Point3::new(1.0, 0.0, 0.0), Point3::new(-1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.0, -1.0, 0.0), Point3::new(0.0, 0.0, stretch), Point3::new(0.0, 0.0, -stretch),
+            Point3::new(0.0, 0.0, 0.0),
+        ]);
+        let mut tetrahedra = Vec::new();
+        for x in [0, 1] {
+            for y in [2, 3] {
+                for z in [4, 5] {
+                    tetrahedra.extend([x, y, z, 6]);
+                }
+            }
+        }
+        xeno.set_tetrahedra(tetrahedra);
+        xeno
+    }
+
+    #[test]
+    fn tetrahedron_and_cube_volumes() {
+        let xeno = cube();
+        assert!((xeno.tetrahedron_volume(0, 1, 3, 4) - 1.0 / 6.0).abs() < 1e-7);
+        assert!((xeno.void_volume_3d(&(0..6).collect()) - 1.0).abs() < 1e-6);
+        assert!((xeno.void_volume_3d(&HashSet::from([0])) - 1.0 / 6.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn merged_tetrahedra_follow_their_points() {
+        let mut left = cube();
+        let mut right = cube();
+        right.points_3d.iter_mut().for_each(|p| p.x += 2.0);
+        right.tetrahedra.extend([0, 1, 2, usize::MAX]);
+
+        left.merge(right);
+        assert_eq!(left.tetrahedra.len(), 13 * 4);
+        assert_eq!(&left.tetrahedra[24..28], &[8, 9, 10, 14]);
+        assert!((left.void_volume_3d(&(0..12).collect()) - 2.0).abs() < 1e-6);
+        assert_eq!(left.tetrahedra[51], usize::MAX);
+    }
+
+    #[test]
+    fn out_of_range_tetrahedra_have_no_volume() {
+        let mut xeno = cube();
+        xeno.tetrahedra.extend([0, 1, 2, usize::MAX]);
+        assert!((xeno.void_volume_3d(&HashSet::from([0, 6])) - 1.0 / 6.0).abs() < 1e-7);
+        assert_eq!(xeno.void_sphericity_3d(&HashSet::from([6])), 0.0);
+
+        // Shrinking the points drops the tetrahedra built on them
+        xeno.set_points_3d(vec![Point3::new(0.0, 0.0, 0.0)]);
+        assert!(xeno.tetrahedra.is_empty());
+        assert_eq!(xeno.void_volume_3d(&(0..7).collect()), 0.0);
+    }
+
+    #[test]
+    fn boundary_faces_form_a_closed_surface() {
+        let mut xeno = cube();
+        // Tetrahedra on an infinite vertex or with a repeated vertex are skipped
+        xeno.tetrahedra.extend([0, 1, 2, 99, 0, 1, 1, 2]);
+        let faces = xeno.boundary_faces();
+        assert_eq!(faces.len(), 12);
+        assert!(is_closed(&faces));
+        assert_eq!(xeno.boundary_tetrahedra(), (0..6).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn regularity_of_equilateral_and_sliver_meshes() {
+        let mut hexagonal = Xenobalanus::new();
+        hexagonal.set_points((0..8 * 8)
+            .map(|i| Point::new(((i % 8) as f32 + (i / 8 % 2) as f32 / 2.0) * 1.0, (i / 8) as f32 * 1.0 * 3f32.sqrt() / 2.0))
+            .collect());
+        hexagonal.delaunay();
+        // Without the obtuse triangles along the zig-zag sides
+        hexagonal.trim_boundary(1.5);
+        assert!((hexagonal.mean_regularity() - 1.0).abs() < 1e-3);
+
+        // Two rows far apart along x and close along y
+        let mut slivers = Xenobalanus::new();
+        slivers.set_points((0..20).map(|i| Point::new(10.0 * (i / 2) as f32 + 5.0 * (i % 2) as f32, 0.5 * (i % 2) as f32)).collect());
+        slivers.delaunay();
+        assert!(slivers.mean_regularity() < 0.3);
+        assert_eq!(Xenobalanus::new().mean_regularity(), 0.0);
+    }
+
+    #[test]
+    fn large_empty_circles_sit_in_the_gaps() {
+        let (xeno, holes) = three_holes();
+        let circles = xeno.empty_circles(2.0);
+        assert!(!circles.is_empty());
+        assert!(circles.iter().all(|&(center, radius)| radius >= 2.0 && holes.iter().any(|h| center.distance(*h) < 2.0)));
+        for hole in &holes {
+            assert!(circles.iter().any(|&(center, _)| center.distance(*hole) < 1.0));
+        }
+    }
+
+    #[test]
+    fn hexagonal_lattice_triangulates_into_equilateral_triangles() {
+        let mut xeno = Xenobalanus::new();
+        xeno.lattice_points(6, 7, 2.0, true);
+        assert_eq!(xeno.points().len(), 42);
+        xeno.delaunay();
+        let report = xeno.mesh_report();
+        assert!((report.min_area - 3f32.sqrt()).abs() < 1e-4 && (report.max_area - 3f32.sqrt()).abs() < 1e-4);
+
+        let mut square = Xenobalanus::new();
+        square.lattice_points(2, 3, 1.0, false);
+        assert_eq!(square.points(), vec![(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (0.0, 1.0), (1.0, 1.0), (2.0, 1.0)]);
+    }
+
+    #[test]
+    fn vertex_coverage_sums_to_three() {
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(grid_with_hole(7, Point::new(-10.0, -10.0), 0.0, 53));
+        xeno.delaunay();
+        let coverage = xeno.vertex_coverage();
+        let n_triangles = xeno.triangles_flat().len() as f32 / 3.0;
+        assert!((coverage.iter().sum::<f32>() - 3.0).abs() < 1e-4);
+        for (v, &fraction) in coverage.iter().enumerate() {
+            let incident = xeno.triangles_flat().iter().filter(|&&u| u == v).count();
+            assert!((fraction * n_triangles - incident as f32).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn min_edge_of_a_scalene_triangle() {
+        let xeno = analyzed(vec![Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(0.0, 3.0)]);
+        assert_eq!(xeno.min_edge(0), (Edge(0, 2), 3.0));
+        assert_eq!(xeno.triangle(0).terminal_edge, Some(Edge(1, 2)));
+    }
+
+    #[test]
+    fn prefix_triangulation_covers_only_the_prefix() {
+        let points = grid_with_hole(10, Point::new(-10.0, -10.0), 0.0, 54);
+        let mut xeno = Xenobalanus::new();
+        xeno.set_points(points.clone());
+        xeno.triangulate_prefix(40);
+        let mut prefix = Xenobalanus::new();
+        prefix.set_points(points[..40].to_vec());
+        prefix.delaunay();
+        assert_eq!(xeno.triangles_flat(), prefix.triangles_flat());
+        assert!(xeno.triangles_flat().iter().all(|&v| v < 40));
+
+        xeno.triangulate_prefix(1000);
+        let full = analyzed(points);
+        assert_eq!(xeno.triangles_flat(), full.triangles_flat());
+        assert_eq!(xeno.halfedges(), full.halfedges());
+    }
+
+    #[test]
+    fn union_hull_encloses_both_clusters() {
+        let mut points = grid_with_hole(6, Point::new(-10.0, -10.0), 0.0, 55);
+        points.extend(grid_with_hole(6, Point::new(-10.0, -10.0), 0.0, 56).iter().map(|p| Point::new(p.x + 6.0, p.y + 2.0)));
+        let xeno = analyzed(points);
+        let (left, right): (Vec<usize>, Vec<usize>) = ((0..36).collect(), (36..72).collect());
+
+        let ring = xeno.clusters_union_hull(&[&left, &right], 0.5).unwrap();
+        assert!(xeno.is_simple_polygon(&ring));
+        let polygon = Polygon::new(ring.iter().map(|&v| Coord::from(xeno.point(v))).collect(), Vec::new());
+        assert!((0..72).all(|v| polygon.intersects(&GeoPoint::new(xeno.point(v).x, xeno.point(v).y))));
+        assert!(matches!(xeno.clusters_union_hull(&[&left, &[72]], 0.5), Err(XenoError::VertexOutOfRange { vertex: 72, points: 72 })));
+    }
+
+    #[test]
+    fn octahedron_normals_point_outwards() {
+        let xeno = octahedron(1.0);
+        let normals = xeno.vertex_normals_3d();
+        let points = xeno.points_3d();
+        for v in 0..6 {
+            let (p, n) = (points[v], normals[v]);
+            assert!((p.0 * n[0] + p.1 * n[1] + p.2 * n[2] - 1.0).abs() < 1e-5, "vertex {}", v);
+        }
+        // The center is not on the surface
+        assert_eq!(normals[6], [0.0; 3]);
+    }
+
+    #[test]
+    fn stretched_void_is_less_spherical() {
+        let void: HashSet<usize> = (0..8).collect();
+        let round = octahedron(1.0).void_sphericity_3d(&void);
+        let stretched = octahedron(5.0).void_sphericity_3d(&void);
+        assert!(round > 0.8 && round < 1.0, "octahedron {}", round);
+        assert!(stretched < 0.8 * round, "stretched {}", stretched);
+        assert_eq!(octahedron(1.0).void_sphericity_3d(&HashSet::new()), 0.0);
+    }
+
+    #[test]
+    fn overlapping_triangles_are_reported() {
+        let points = vec![Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(0.0, 2.0), Point::new(0.5, 0.5), Point::new(3.0, 1.0), Point::new(1.0, 3.0), Point::new(2.0, 2.0)];
+        let mut xeno = Xenobalanus::new();
+        // 0 and 1 share the edge 1-2, 2 overlaps both
+        xeno.set_mesh(points, vec![0, 1, 2, 1, 6, 2, 3, 4, 5]).unwrap();
+        assert_eq!(xeno.find_overlapping_triangles(), vec![(0, 2), (1, 2)]);
+
+        let grid = analyzed(grid_with_hole(10, Point::new(-10.0, -10.0), 0.0, 57));
+        assert!(grid.find_overlapping_triangles().is_empty());
+    }
+
+    #[test]
+    fn builder_output_is_ready_for_delfin() {
+        let xeno = Xenobalanus::builder()
+            .points(grid_with_hole(20, Point::new(10.0, 10.0), 4.0, 58))
+            .preprocess_mode(PreprocessMode::Voids)
+            .metric(Metric::Euclidean)
+            .parallel(true)
+            .build()
+            .unwrap();
+        let voids = xeno.delfin(20.0, 2.0).unwrap();
+        assert_eq!(voids.len(), 1);
+        assert!(xeno.vertex_connections().is_empty());
+
+        assert!(matches!(Xenobalanus::builder().build(), Err(XenoError::NoPoints)));
+        let attractors = Xenobalanus::builder().points(grid_with_hole(5, Point::new(-10.0, -10.0), 0.0, 59)).preprocess_mode(PreprocessMode::Attractors).build().unwrap();
+        assert!(matches!(attractors.delfin(0.0, 1.0), Err(XenoError::AreasNotComputed)));
+    }
+
+    #[test]
+    fn spherical_delaunay_is_seamless_across_the_antimeridian() {
+        // Jittered grid over lon 179..181, lat -1..1, stored in [-180, 180)
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut unwrapped: Vec<Point> = Vec::new();
+        for i in 0..=10 {
+            for j in 0..=10 {
+                let lon = 179.0 + 0.2 * i as f32 + rng.gen_range(-0.03..0.03);
+                let lat = -1.0 + 0.2 * j as f32 + rng.gen_range(-0.03..0.03);
+                unwrapped.push(Point::new(lon, lat));
+            }
+        }
+        let wrapped = unwrapped.iter()
+            .map(|p| (if p.x >= 180.0 { p.x - 360.0 } else { p.x }, p.y))
+            .collect();
+
+        let mut sphere = Xenobalanus::new();
+        sphere.set_points_lonlat(wrapped);
+        sphere.delaunay_spherical();
+        let mut plane = Xenobalanus::new();
+        plane.set_points(unwrapped.clone());
+        plane.delaunay();
+        plane.preprocess(0, false);
+
+        // No faces closing the far side of the globe and the same mesh as in the
+        // unwrapped plane, so nothing breaks at the seam
+        let edges = |xeno: &Xenobalanus| -> HashSet<Edge> {
+            xeno.triangles_flat().chunks(3)
+                .flat_map(triangle_edges)
+                .map(|(a, b)| Edge(min(a, b), max(a, b)))
+                .collect()
+        };
+        assert_eq!(sphere.triangles_flat().len(), plane.triangles_flat().len());
+        assert_eq!(edges(&sphere), edges(&plane));
+
+        // Near the equator square degrees on the sphere match the planar ones
+        sphere.preprocess(0, false);
+        let area = |xeno: &Xenobalanus| xeno.iter_triangles().filter_map(|(_, t)| t.area).sum::<f32>();
+        assert!((area(&sphere) - area(&plane)).abs() < 0.01 * area(&plane));
+    }
+
+    #[test]
+    fn large_void_persists_while_small_ones_vanish() {
+        let xeno = analyzed(grid_with_hole(25, Point::new(12.0, 12.0), 5.0, 60));
+        let tracks = xeno.void_persistence(&[3.0, 1.0, 1.5, 2.0, 4.0], 0.0).unwrap();
+        let (big, rest): (Vec<VoidTrack>, Vec<VoidTrack>) = tracks.into_iter()
+            .partition(|track| track.centroid.distance(Point::new(12.0, 12.0)) < 2.0);
+
+        assert_eq!(big.len(), 1);
+        assert_eq!((big[0].birth, big[0].death), (1.0, None));
+        // Slivers along the jittered hull have long edges too, interior voids do not
+        let interior: Vec<&VoidTrack> = rest.iter()
+            .filter(|track| (1.0..23.0).contains(&track.centroid.x) && (1.0..23.0).contains(&track.centroid.y))
+            .collect();
+        assert!(interior.len() > 10);
+        assert!(interior.iter().all(|track| track.birth == 1.0 && track.death.is_some_and(|death| death <= 2.0)));
+        assert!(rest.iter().all(|track| track.area < 0.05 * big[0].area));
+        // Raising the threshold only removes seeds, so no void is born late
+        assert!(rest.iter().all(|track| track.birth == 1.0));
+    }
+
+    #[test]
+    fn quality_of_equilateral_and_sliver_triangles() {
+        let points = vec![Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(1.0, 3f32.sqrt()), Point::new(1.0, -0.01), Point::new(1.0, 1.0)];
+        let mut xeno = Xenobalanus::new();
+        xeno.set_mesh(points, vec![0, 1, 2, 1, 0, 3, 0, 4, 4]).unwrap();
+        let quality = xeno.quality_field();
+        assert!((quality[0] - 1.0).abs() < 1e-4);
+        assert!(quality[1] < 0.02);
+        assert_eq!(quality[2], 0.0);
+
+        // Every shifted row end of a hexagonal lattice leaves one 30-30-120 triangle along the sides
+        let mut lattice = Xenobalanus::new();
+        lattice.lattice_points(6, 7, 2.0, true);
+        lattice.delaunay();
+        let quality = lattice.quality_field();
+        assert_eq!(quality.iter().filter(|&&q| (q - 1.0).abs() < 1e-4).count(), 60);
+        assert!(quality.iter().all(|&q| (q - 1.0).abs() < 1e-4 || (q - 0.5).abs() < 1e-4));
+    }
+
+    #[test]
+    fn spine_runs_along_an_elongated_void() {
+        let mut points = grid_with_hole(30, Point::new(-10.0, -10.0), 0.0, 61);
+        points.retain(|p| !(p.x > 6.0 && p.x < 24.0 && p.y > 13.0 && p.y < 16.0));
+        let xeno = analyzed(points);
+        let void = xeno.largest_void(2.0).unwrap().unwrap();
+        let spine = xeno.void_spine(&void);
+
+        assert!(spine.iter().all(|t| void.contains(t)));
+        assert!(spine.windows(2).all(|w| xeno.triangle(w[0]).edges.iter().any(|e| xeno.triangle(w[1]).edges.contains(e))));
+        let centroid = |i: usize| centroid_of(&xeno, &xeno.triangle(i).vertices);
+        let span = centroid(spine[0]).distance(centroid(*spine.last().unwrap()));
+        let xs: Vec<f32> = void.iter().map(|&i| centroid(i).x).collect();
+        let extent = xs.iter().cloned().fold(f32::MIN, f32::max) - xs.iter().cloned().fold(f32::MAX, f32::min);
+        assert!(span > 0.6 * extent, "spine spans {} of {}", span, extent);
+    }
+
+    #[test]
+    fn gap_between_two_clusters() {
+        let mut xeno = Xenobalanus::new();
+        xeno.lattice_points(5, 5, 1.0, false);
+        let mut right: Vec<Point> = xeno.points().iter().map(|&(x, y)| Point::new(x + 10.0, y)).collect();
+        right.push(Point::new(6.5, 2.5));
+        xeno.add_points(right);
+        let (a, b): (Vec<usize>, Vec<usize>) = ((0..25).collect(), (25..51).collect());
+
+        let (u, v, distance) = xeno.cluster_gap(&a, &b);
+        assert_eq!(v, 50);
+        assert!([14, 19].contains(&u));
+        assert!((distance - 2.5f32.hypot(0.5)).abs() < 1e-5);
+        assert_eq!(xeno.cluster_gap(&a, &[]), (usize::MAX, usize::MAX, f32::INFINITY));
+    }
+
+    #[test]
+    fn rasterized_cells_carry_their_void_label() {
+        let mut xeno = Xenobalanus::new();
+        xeno.lattice_points(5, 5, 1.0, false);
+        xeno.delaunay();
+        xeno.preprocess(0, false);
+        let left: HashSet<usize> = xeno.iter_triangles()
+            .filter(|(_, t)| centroid_of(&xeno, &t.vertices).x < 2.0)
+            .map(|(i, _)| i)
+            .collect();
+        let (labels, columns, rows) = xeno.rasterize_voids(&[left], 0.5);
+        assert_eq!((columns, rows), (8, 8));
+        for (cell, &label) in labels.iter().enumerate() {
+            assert_eq!(label, if cell % columns < 4 { 1 } else { 0 }, "cell {}", cell);
+        }
+
+        let (xeno, holes) = three_holes();
+        let voids = xeno.delfin(8.0, 2.0).unwrap();
+        let (labels, columns, _) = xeno.rasterize_voids(&voids, 1.0);
+        let (lo, hi) = xeno.bounding_box().unwrap();
+        for (id, void) in voids.iter().enumerate() {
+            let hole = holes[hole_of(&xeno, void, &holes)];
+            let cell = ((hi.y - hole.y) as usize) * columns + (hole.x - lo.x) as usize;
+            assert_eq!(labels[cell], id as u32 + 1);
+        }
+    }
+}